@@ -0,0 +1,412 @@
+use crate::{
+    Error, MinPk, MinSig, MultiPublicKeyOf, MultiSignatureOf, Point, PublicKeyOf, Scheme,
+    SecretKey, SignatureOf, Validated,
+};
+use alloc::vec::Vec;
+use bls12_381_plus::{ExpandMsgXmd, Scalar};
+use ff::Field;
+use group::Group;
+use serde::{Deserialize, Deserializer, Serialize};
+use subtle::{Choice, CtOption};
+
+/// A fixed group of signers for accountable-subgroup multisignatures
+/// as described in <https://eprint.iacr.org/2018/483.pdf> Section 5.
+///
+/// The group key is `apk = \sum a_i \cdot pk_i` where `a_i = H_1(pk_i, {pk_1, ..., pk_n})`
+/// which prevents rogue key attacks without requiring proofs of possession.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+#[serde(bound(serialize = "PublicKeyOf<S>: Serialize, MultiPublicKeyOf<S>: Serialize"))]
+pub struct AccountableGroupOf<S: Scheme> {
+    /// The ordered members of the group
+    pub keys: Vec<PublicKeyOf<S>>,
+    /// The aggregate group key
+    pub apk: MultiPublicKeyOf<S>,
+}
+
+/// A fixed group of signers with public keys in G2
+pub type AccountableGroup = AccountableGroupOf<MinSig>;
+
+/// A fixed group of signers with public keys in G1
+pub type AccountableGroupVt = AccountableGroupOf<MinPk>;
+
+/// A contribution from one member toward another member's membership key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MembershipKeyShareOf<S: Scheme>(pub S::SignatureGroup);
+
+/// A contribution toward a membership key in G1
+pub type MembershipKeyShare = MembershipKeyShareOf<MinSig>;
+
+/// A contribution toward a membership key in G2
+pub type MembershipKeyShareVt = MembershipKeyShareOf<MinPk>;
+
+display_one_impl!(MembershipKeyShareOf<S>);
+
+serde_impl!(MembershipKeyShareOf<S>, SignatureGroup);
+
+uncompressed_impl!(MembershipKeyShareOf<S>, SignatureGroup);
+
+cond_select_impl!(MembershipKeyShareOf<S>);
+
+impl<S: Scheme> MembershipKeyShareOf<S> {
+    /// Number of bytes needed to represent the share
+    pub const BYTES: usize = <S::SignatureGroup as Point>::BYTES;
+
+    validity_checks!();
+
+    bytes_impl!(S::SignatureGroup);
+}
+
+/// A member's key proving membership in an [`AccountableGroupOf`].
+/// This is computed once in the setup phase and kept private.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MembershipKeyOf<S: Scheme>(pub S::SignatureGroup);
+
+/// A membership key in G1
+pub type MembershipKey = MembershipKeyOf<MinSig>;
+
+/// A membership key in G2
+pub type MembershipKeyVt = MembershipKeyOf<MinPk>;
+
+display_one_impl!(MembershipKeyOf<S>);
+
+serde_impl!(MembershipKeyOf<S>, SignatureGroup);
+
+uncompressed_impl!(MembershipKeyOf<S>, SignatureGroup);
+
+cond_select_impl!(MembershipKeyOf<S>);
+
+impl<S: Scheme> MembershipKeyOf<S> {
+    /// Number of bytes needed to represent the membership key
+    pub const BYTES: usize = <S::SignatureGroup as Point>::BYTES;
+
+    validity_checks!();
+
+    bytes_impl!(S::SignatureGroup);
+
+    /// Combine the shares sent by every member of the group
+    pub fn from_shares(shares: &[MembershipKeyShareOf<S>]) -> Self {
+        let mut g = S::SignatureGroup::identity();
+        for s in shares {
+            g += s.0;
+        }
+        Self(g)
+    }
+
+    /// Verify this is the membership key for the member at `index` in `group`
+    pub fn verify(&self, group: &AccountableGroupOf<S>, index: usize) -> Choice {
+        if (self.is_invalid() | group.apk.is_invalid()).unwrap_u8() == 1u8 {
+            return 0u8.into();
+        }
+        let h = AccountableGroupOf::hash_index(&group.apk, index);
+
+        S::pairing_check(&[
+            (&S::signature_input(&h), &S::public_key_input(&group.apk.0)),
+            (&S::signature_input(&self.0), &S::neg_generator()),
+        ])
+    }
+}
+
+/// An accountable-subgroup multisignature.
+/// The subgroup key is derived from the group and the signer indices
+/// when verifying so the signers are bound to the subgroup.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(bound(
+    serialize = "MultiSignatureOf<S>: Serialize",
+    deserialize = "MultiSignatureOf<S>: Deserialize<'de>"
+))]
+pub struct AccountableSignatureOf<S: Scheme> {
+    /// The aggregated signature of the subgroup
+    pub signature: MultiSignatureOf<S>,
+}
+
+/// An accountable-subgroup multisignature in G1
+pub type AccountableSignature = AccountableSignatureOf<MinSig>;
+
+/// An accountable-subgroup multisignature in G2
+pub type AccountableSignatureVt = AccountableSignatureOf<MinPk>;
+
+impl<S: Scheme> AccountableGroupOf<S> {
+    /// The domain separation tag for computing key coefficients
+    const COEFFICIENT_DST: &'static [u8] = b"BLS_ASM_BLS12381_COEFFICIENT_";
+
+    /// Create a new group from the ordered list of member keys
+    pub fn new(keys: &[PublicKeyOf<S>]) -> Option<Self> {
+        Self::try_new(keys).ok()
+    }
+
+    /// Create a new group from the ordered list of member keys
+    /// and return the reason if it fails
+    pub fn try_new(keys: &[PublicKeyOf<S>]) -> Result<Self, Error> {
+        if keys.is_empty() {
+            return Err(Error::Empty);
+        }
+        for k in keys {
+            k.validate()?;
+        }
+        let mut apk = S::PublicKeyGroup::identity();
+        for (i, k) in keys.iter().enumerate() {
+            apk += k.0 * Self::coefficient(keys, i);
+        }
+        Ok(Self {
+            keys: keys.to_vec(),
+            apk: MultiPublicKeyOf(apk),
+        })
+    }
+
+    /// The index of `pk` in this group
+    pub fn index_of(&self, pk: &PublicKeyOf<S>) -> Option<usize> {
+        self.keys.iter().position(|k| k == pk)
+    }
+
+    /// Compute the share of the membership key for member `index`
+    /// using this member's secret key
+    pub fn membership_key_share(
+        &self,
+        sk: &SecretKey,
+        index: usize,
+    ) -> Option<MembershipKeyShareOf<S>> {
+        if sk.0.is_zero().unwrap_u8() == 1u8 || index >= self.keys.len() {
+            return None;
+        }
+        let signer = self.index_of(&PublicKeyOf::from(sk))?;
+        let a = Self::coefficient(&self.keys, signer);
+        let h = Self::hash_index(&self.apk, index);
+        Some(MembershipKeyShareOf(h * (a * sk.0)))
+    }
+
+    /// Sign `msg` as a member of this group
+    pub fn sign<B: AsRef<[u8]>>(
+        &self,
+        sk: &SecretKey,
+        mk: &MembershipKeyOf<S>,
+        msg: B,
+    ) -> Option<SignatureOf<S>> {
+        if sk.0.is_zero().unwrap_u8() == 1u8 || mk.is_invalid().unwrap_u8() == 1u8 {
+            return None;
+        }
+        let a = self.hash_msg(msg.as_ref());
+        Some(SignatureOf(a * sk.0 + mk.0))
+    }
+
+    /// Combine the signatures from the subgroup at `indices` into
+    /// an accountable signature. `signatures[i]` must be from the
+    /// member at `indices[i]`. Returns `None` if any signature is invalid.
+    pub fn combine(
+        &self,
+        indices: &[usize],
+        signatures: &[SignatureOf<S>],
+    ) -> Option<AccountableSignatureOf<S>> {
+        self.try_combine(indices, signatures).ok()
+    }
+
+    /// Combine the signatures from the subgroup at `indices` into
+    /// an accountable signature and return the reason if it fails
+    pub fn try_combine(
+        &self,
+        indices: &[usize],
+        signatures: &[SignatureOf<S>],
+    ) -> Result<AccountableSignatureOf<S>, Error> {
+        if indices.is_empty() {
+            return Err(Error::Empty);
+        }
+        if indices.len() != signatures.len() {
+            return Err(Error::InvalidLength {
+                expected: indices.len(),
+                actual: signatures.len(),
+            });
+        }
+        if let Some(i) = indices.iter().find(|i| **i >= self.keys.len()) {
+            return Err(Error::InvalidIndex(*i));
+        }
+        let signatures = signatures
+            .iter()
+            .map(|s| Validated::new(*s))
+            .collect::<Result<Vec<_>, Error>>()?;
+        Ok(AccountableSignatureOf {
+            signature: MultiSignatureOf::from(signatures.as_slice()),
+        })
+    }
+
+    /// The aggregate key of the members at `indices`
+    fn subgroup_key(&self, indices: &[usize]) -> S::PublicKeyGroup {
+        let mut pk = S::PublicKeyGroup::identity();
+        for i in indices {
+            pk += self.keys[*i].0;
+        }
+        pk
+    }
+
+    fn hash_msg(&self, msg: &[u8]) -> S::SignatureGroup {
+        let mut data = Vec::with_capacity(MultiPublicKeyOf::<S>::BYTES + msg.len());
+        data.extend_from_slice(self.apk.to_bytes().as_ref());
+        data.extend_from_slice(msg);
+        S::SignatureGroup::hash_to_curve::<ExpandMsgXmd<sha2::Sha256>>(&data, S::ASM_MSG_DST)
+    }
+
+    fn hash_index(apk: &MultiPublicKeyOf<S>, index: usize) -> S::SignatureGroup {
+        let mut data = Vec::with_capacity(MultiPublicKeyOf::<S>::BYTES + 8);
+        data.extend_from_slice(apk.to_bytes().as_ref());
+        data.extend_from_slice(&(index as u64).to_le_bytes());
+        S::SignatureGroup::hash_to_curve::<ExpandMsgXmd<sha2::Sha256>>(&data, S::ASM_INDEX_DST)
+    }
+
+    fn coefficient(keys: &[PublicKeyOf<S>], index: usize) -> Scalar {
+        const INFO: [u8; 2] = [0u8, 48u8];
+
+        let mut extractor = hkdf::HkdfExtract::<sha2::Sha256>::new(Some(Self::COEFFICIENT_DST));
+        extractor.input_ikm(keys[index].to_bytes().as_ref());
+        for k in keys {
+            extractor.input_ikm(k.to_bytes().as_ref());
+        }
+        extractor.input_ikm(&[0u8]);
+        let (_, h) = extractor.finalize();
+
+        let mut output = [0u8; 48];
+        // 48 bytes is acceptable length so `unwrap` is okay
+        h.expand(&INFO, &mut output).unwrap();
+        Scalar::from_okm(&output)
+    }
+}
+
+impl<'de, S: Scheme> Deserialize<'de> for AccountableGroupOf<S> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(
+            rename = "AccountableGroup",
+            bound(deserialize = "PublicKeyOf<S>: Deserialize<'de>, \
+                                 MultiPublicKeyOf<S>: Deserialize<'de>")
+        )]
+        struct Raw<S: Scheme> {
+            keys: Vec<PublicKeyOf<S>>,
+            apk: MultiPublicKeyOf<S>,
+        }
+
+        let raw = Raw::<S>::deserialize(d)?;
+        let group =
+            Self::new(&raw.keys).ok_or_else(|| serde::de::Error::custom("invalid group keys"))?;
+        if group.apk != raw.apk {
+            return Err(serde::de::Error::custom("group key mismatch"));
+        }
+        Ok(group)
+    }
+}
+
+impl<S: Scheme> AccountableSignatureOf<S> {
+    /// Verify this signature is over `msg` by the members at `indices` in `group`
+    pub fn verify<B: AsRef<[u8]>>(
+        &self,
+        group: &AccountableGroupOf<S>,
+        indices: &[usize],
+        msg: B,
+    ) -> Choice {
+        if (self.signature.is_invalid() | group.apk.is_invalid()).unwrap_u8() == 1u8 {
+            return 0u8.into();
+        }
+        if indices.is_empty() || indices.iter().any(|i| *i >= group.keys.len()) {
+            return 0u8.into();
+        }
+        // Each member may only be counted once
+        for (j, i) in indices.iter().enumerate() {
+            if indices[..j].contains(i) {
+                return 0u8.into();
+            }
+        }
+        let mut h = S::SignatureGroup::identity();
+        for i in indices {
+            h += AccountableGroupOf::hash_index(&group.apk, *i);
+        }
+        let pk = group.subgroup_key(indices);
+        let a = group.hash_msg(msg.as_ref());
+
+        S::pairing_check(&[
+            (&S::signature_input(&a), &S::public_key_input(&pk)),
+            (&S::signature_input(&h), &S::public_key_input(&group.apk.0)),
+            (&S::signature_input(&self.signature.0), &S::neg_generator()),
+        ])
+    }
+}
+
+#[test]
+fn accountable_subgroup_works() {
+    use crate::{MockRng, PublicKey, PublicKeyVt};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([11u8; 16]);
+    let sks = (0..4)
+        .map(|_| SecretKey::random(&mut rng).unwrap())
+        .collect::<Vec<_>>();
+    let pks = sks.iter().map(PublicKey::from).collect::<Vec<_>>();
+    let group = AccountableGroup::new(&pks).unwrap();
+
+    // One-time setup where every member sends a share to every other member
+    let mks = (0..pks.len())
+        .map(|j| {
+            let shares = sks
+                .iter()
+                .map(|sk| group.membership_key_share(sk, j).unwrap())
+                .collect::<Vec<_>>();
+            MembershipKey::from_shares(&shares)
+        })
+        .collect::<Vec<_>>();
+    for (j, mk) in mks.iter().enumerate() {
+        assert_eq!(mk.verify(&group, j).unwrap_u8(), 1u8);
+    }
+    assert_eq!(mks[0].verify(&group, 1).unwrap_u8(), 0u8);
+
+    let msg = b"accountable_subgroup";
+    let indices = [0usize, 2];
+    let sigs = indices
+        .iter()
+        .map(|i| group.sign(&sks[*i], &mks[*i], msg).unwrap())
+        .collect::<Vec<_>>();
+    let asig = group.combine(&indices, &sigs).unwrap();
+    assert_eq!(asig.verify(&group, &indices, msg).unwrap_u8(), 1u8);
+    assert_eq!(asig.verify(&group, &[0, 1], msg).unwrap_u8(), 0u8);
+    assert_eq!(asig.verify(&group, &[0, 2, 2], msg).unwrap_u8(), 0u8);
+    assert_eq!(asig.verify(&group, &indices, b"bad").unwrap_u8(), 0u8);
+
+    // Even with a leaked membership key, a subgroup signature
+    // can't name members whose secret keys weren't used
+    let sig = group.sign(&sks[0], &mks[0], msg).unwrap();
+    let forged = AccountableSignature {
+        signature: MultiSignatureOf(sig.0 + mks[1].0),
+    };
+    assert_eq!(forged.verify(&group, &[0, 1], msg).unwrap_u8(), 0u8);
+
+    let group_bytes = serde_bare::to_vec(&group).unwrap();
+    let de_group = serde_bare::from_slice::<AccountableGroup>(&group_bytes).unwrap();
+    assert_eq!(de_group, group);
+    let mut bad_group = group.clone();
+    bad_group.apk = MultiPublicKeyOf(pks[0].0);
+    let bad_bytes = serde_bare::to_vec(&bad_group).unwrap();
+    assert!(serde_bare::from_slice::<AccountableGroup>(&bad_bytes).is_err());
+
+    let pks = sks.iter().map(PublicKeyVt::from).collect::<Vec<_>>();
+    let group = AccountableGroupVt::new(&pks).unwrap();
+    let mks = (0..pks.len())
+        .map(|j| {
+            let shares = sks
+                .iter()
+                .map(|sk| group.membership_key_share(sk, j).unwrap())
+                .collect::<Vec<_>>();
+            MembershipKeyVt::from_shares(&shares)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(mks[3].verify(&group, 3).unwrap_u8(), 1u8);
+    let indices = [1usize, 3];
+    let sigs = indices
+        .iter()
+        .map(|i| group.sign(&sks[*i], &mks[*i], msg).unwrap())
+        .collect::<Vec<_>>();
+    let asig = group.combine(&indices, &sigs).unwrap();
+    assert_eq!(asig.verify(&group, &indices, msg).unwrap_u8(), 1u8);
+    assert_eq!(asig.verify(&group, &[1, 2], msg).unwrap_u8(), 0u8);
+    let group_bytes = serde_bare::to_vec(&group).unwrap();
+    assert_eq!(
+        serde_bare::from_slice::<AccountableGroupVt>(&group_bytes).unwrap(),
+        group
+    );
+}
@@ -0,0 +1,139 @@
+use crate::{MinPk, MinSig, Point, PublicKeyOf, Scheme, SignatureOf};
+use alloc::vec::Vec;
+use bls12_381_plus::Scalar;
+use ff::Field;
+use group::Group;
+use serde::{Deserialize, Serialize};
+use subtle::Choice;
+
+/// A proof of knowledge of an aggregate signature
+/// over several messages under several keys
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
+#[serde(bound = "S::SignatureGroup: crate::ByteEncoding")]
+pub struct AggregateProofOfKnowledgeOf<S: Scheme> {
+    /// x \cdot A_i for each message
+    #[serde(with = "crate::serdes::canonical_vec")]
+    pub u: Vec<S::SignatureGroup>,
+    /// V = -(x + y) \cdot \sigma
+    #[serde(
+        serialize_with = "crate::serdes::serialize",
+        deserialize_with = "crate::serdes::deserialize"
+    )]
+    pub v: S::SignatureGroup,
+}
+
+/// A proof of knowledge of an aggregate signature in G1
+pub type AggregateProofOfKnowledge = AggregateProofOfKnowledgeOf<MinSig>;
+
+/// A proof of knowledge of an aggregate signature in G2
+pub type AggregateProofOfKnowledgeVt = AggregateProofOfKnowledgeOf<MinPk>;
+
+impl<S: Scheme> AggregateProofOfKnowledgeOf<S> {
+    /// Check if this is valid, i.e. there is at least one `u` and every point
+    /// is not the identity, is on the curve and is in the prime order subgroup
+    pub fn is_valid(&self) -> Choice {
+        !self.is_invalid()
+    }
+
+    /// Check if this is invalid
+    pub fn is_invalid(&self) -> Choice {
+        let mut res = Choice::from(self.u.is_empty() as u8);
+        for p in self.u.iter().chain(core::iter::once(&self.v)) {
+            res |= p.is_identity() | !p.is_on_curve() | !p.is_torsion_free();
+        }
+        res
+    }
+
+    /// Verify the proof of knowledge where `data` lists the
+    /// public key and message for each signature in the aggregate
+    /// in the same order used to create the proof
+    pub fn verify<B: AsRef<[u8]>>(&self, data: &[(PublicKeyOf<S>, B)], y: Scalar) -> Choice {
+        if self.is_invalid().unwrap_u8() == 1u8 || self.u.len() != data.len() {
+            return 0u8.into();
+        }
+        if y.is_zero().unwrap_u8() == 1u8 {
+            return 0u8.into();
+        }
+        if data.iter().any(|(k, _)| k.is_invalid().unwrap_u8() == 1u8) {
+            return 0u8.into();
+        }
+
+        let mut pairs = data
+            .iter()
+            .zip(self.u.iter())
+            .map(|((key, m), u)| {
+                let a = SignatureOf::<S>::hash_msg(m.as_ref());
+                (
+                    S::signature_input(&(*u + a * y)),
+                    S::public_key_input(&key.0),
+                )
+            })
+            .collect::<Vec<(S::SignatureInput, S::PublicKeyInput)>>();
+        pairs.push((
+            S::signature_input(&self.v),
+            S::public_key_input(&S::PublicKeyGroup::generator()),
+        ));
+        // appease borrow checker
+        let t = pairs
+            .iter()
+            .map(|(p1, p2)| (p1, p2))
+            .collect::<Vec<(&S::SignatureInput, &S::PublicKeyInput)>>();
+        S::pairing_check(t.as_slice())
+    }
+}
+
+#[test]
+fn aggregate_proof_works() {
+    use crate::{AggregateSignature, MockRng, PublicKey, SecretKey, Signature, Validated};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([23u8; 16]);
+    let mut data = Vec::new();
+    let mut sigs = Vec::new();
+    for i in 0..3u8 {
+        let sk = SecretKey::random(&mut rng).unwrap();
+        let msg = [i; 16];
+        sigs.push(Validated::new(Signature::new(&sk, msg).unwrap()).unwrap());
+        data.push((PublicKey::from(&sk), msg));
+    }
+    let sig = AggregateSignature::from(sigs.as_slice());
+    let msgs = data.iter().map(|(_, m)| *m).collect::<Vec<_>>();
+
+    let x = Scalar::random(&mut rng);
+    let y = Scalar::random(&mut rng);
+    let proof = sig.proof_of_knowledge(&msgs, x, y).unwrap();
+    assert_eq!(proof.verify(&data, y).unwrap_u8(), 1u8);
+    assert_eq!(proof.verify(&data, x).unwrap_u8(), 0u8);
+    assert_eq!(proof.verify(&data[..2], y).unwrap_u8(), 0u8);
+
+    data.swap(0, 1);
+    assert_eq!(proof.verify(&data, y).unwrap_u8(), 0u8);
+}
+
+#[test]
+fn aggregate_proof_vt_works() {
+    use crate::{AggregateSignatureVt, MockRng, PublicKeyVt, SecretKey, SignatureVt, Validated};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([23u8; 16]);
+    let mut data = Vec::new();
+    let mut sigs = Vec::new();
+    for i in 0..3u8 {
+        let sk = SecretKey::random(&mut rng).unwrap();
+        let msg = [i; 16];
+        sigs.push(Validated::new(SignatureVt::new(&sk, msg).unwrap()).unwrap());
+        data.push((PublicKeyVt::from(&sk), msg));
+    }
+    let sig = AggregateSignatureVt::from(sigs.as_slice());
+    let msgs = data.iter().map(|(_, m)| *m).collect::<Vec<_>>();
+
+    let x = Scalar::random(&mut rng);
+    let y = Scalar::random(&mut rng);
+    let proof = sig.proof_of_knowledge(&msgs, x, y).unwrap();
+    assert_eq!(proof.verify(&data, y).unwrap_u8(), 1u8);
+    assert_eq!(proof.verify(&data, x).unwrap_u8(), 0u8);
+    assert_eq!(proof.verify(&data[..2], y).unwrap_u8(), 0u8);
+
+    data.swap(0, 1);
+    assert_eq!(proof.verify(&data, y).unwrap_u8(), 0u8);
+}
@@ -1,91 +1,281 @@
-use crate::{PublicKey, Signature};
-use bls12_381_plus::{G1Affine, G1Projective, G2Affine};
-use group::{Curve, Group};
+use crate::{
+    HashedMessageOf, MinPk, MinSig, PreparedPublicKeyOf, PublicKeyOf, Scheme, SignatureOf,
+    Validated,
+};
+use group::Group;
 use subtle::{Choice, CtOption};
 
-/// Represents a BLS signature in G1 for multiple signatures that signed the different messages
+/// Represents a BLS signature for multiple signatures that signed the different messages
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub struct AggregateSignature(pub G1Projective);
+pub struct AggregateSignatureOf<S: Scheme>(pub S::SignatureGroup);
+
+/// Represents a BLS signature in G1 for multiple signatures that signed the different messages
+pub type AggregateSignature = AggregateSignatureOf<MinSig>;
+
+/// Represents a BLS signature in G2 for multiple signatures that signed the different messages
+pub type AggregateSignatureVt = AggregateSignatureOf<MinPk>;
 
-display_one_impl!(AggregateSignature);
+display_one_impl!(AggregateSignatureOf<S>);
 
-impl From<&[Signature]> for AggregateSignature {
-    fn from(sigs: &[Signature]) -> Self {
-        let mut g = G1Projective::IDENTITY;
+impl<S: Scheme> From<&[Validated<SignatureOf<S>>]> for AggregateSignatureOf<S> {
+    fn from(sigs: &[Validated<SignatureOf<S>>]) -> Self {
+        let mut g = S::SignatureGroup::identity();
         for s in sigs {
-            g += s.0;
+            g += s.as_ref().0;
         }
         Self(g)
     }
 }
 
-serde_impl!(AggregateSignature, G1Projective);
+serde_impl!(AggregateSignatureOf<S>, SignatureGroup);
 
-cond_select_impl!(AggregateSignature, G1Projective);
+uncompressed_impl!(AggregateSignatureOf<S>, SignatureGroup);
 
-impl AggregateSignature {
+cond_select_impl!(AggregateSignatureOf<S>);
+
+impl<S: Scheme> AggregateSignatureOf<S> {
     /// Number of bytes needed to represent the signature
-    pub const BYTES: usize = 48;
+    pub const BYTES: usize = <S::SignatureGroup as crate::Point>::BYTES;
 
     validity_checks!();
 
-    bytes_impl!(G1Affine, G1Projective);
+    bytes_impl!(S::SignatureGroup);
 
     /// Verify this multi signature is over `msg` with the multi public key
-    pub fn verify<B: AsRef<[u8]>>(&self, data: &[(PublicKey, B)]) -> Choice {
+    pub fn verify<B: AsRef<[u8]>>(&self, data: &[(PublicKeyOf<S>, B)]) -> Choice {
         if self.is_invalid().unwrap_u8() == 1 {
             return Choice::from(0u8);
         }
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
 
-        #[cfg(not(feature = "alloc"))]
-        fn core_aggregate_verify<B: AsRef<[u8]>>(
-            sig: &G1Projective,
-            data: &[(PublicKey, B)],
-        ) -> Choice {
-            use bls12_381_plus::{pairing, Gt};
+            // Borrow the messages first so `B` doesn't need to be `Sync`
+            let msgs = data
+                .iter()
+                .map(|(key, m)| (key, m.as_ref()))
+                .collect::<Vec<(&PublicKeyOf<S>, &[u8])>>();
+            let hashed = msgs
+                .par_iter()
+                .map(|(key, m)| (*key, SignatureOf::<S>::hash_msg(m)))
+                .collect::<Vec<_>>();
+            self.par_aggregate_verify(&hashed)
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.core_aggregate_verify(
+                data.iter()
+                    .map(|(key, m)| (key, SignatureOf::<S>::hash_msg(m.as_ref()))),
+            )
+        }
+    }
 
-            let mut res = Gt::IDENTITY;
-            for (key, msg) in data {
-                if key.is_invalid().unwrap_u8() == 1 {
-                    return Choice::from(0u8);
-                }
-                let a = Signature::hash_msg(msg.as_ref());
-                res += pairing(&a.to_affine(), &key.0.to_affine());
+    /// Verify this aggregate signature is over each already hashed `msg` with the public keys
+    pub fn verify_prehashed(&self, data: &[(PublicKeyOf<S>, HashedMessageOf<S>)]) -> Choice {
+        if self.is_invalid().unwrap_u8() == 1 {
+            return Choice::from(0u8);
+        }
+        if data.iter().any(|(_, m)| !m.is_signature_dst()) {
+            return Choice::from(0u8);
+        }
+        #[cfg(feature = "parallel")]
+        {
+            let hashed = data
+                .iter()
+                .map(|(key, m)| (key, m.point))
+                .collect::<Vec<_>>();
+            self.par_aggregate_verify(&hashed)
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            self.core_aggregate_verify(data.iter().map(|(key, m)| (key, m.point)))
+        }
+    }
+
+    /// Verify this aggregate signature is over each `msg` with the public keys
+    /// and return the reason if it isn't
+    pub fn try_verify<B: AsRef<[u8]>>(
+        &self,
+        data: &[(PublicKeyOf<S>, B)],
+    ) -> Result<(), crate::Error> {
+        self.validate()?;
+        for (key, _) in data {
+            key.validate()?;
+        }
+        crate::Error::verified(self.verify(data))
+    }
+
+    /// Verify this aggregate signature is over each already hashed `msg` with the public keys
+    /// and return the reason if it isn't
+    pub fn try_verify_prehashed(
+        &self,
+        data: &[(PublicKeyOf<S>, HashedMessageOf<S>)],
+    ) -> Result<(), crate::Error> {
+        self.validate()?;
+        for (key, msg) in data {
+            key.validate()?;
+            if !msg.is_signature_dst() {
+                return Err(crate::Error::InvalidDst);
             }
-            res += pairing(&sig.to_affine(), &-G2Affine::generator());
-            res.is_identity()
         }
-        #[cfg(feature = "alloc")]
-        fn core_aggregate_verify<B: AsRef<[u8]>>(
-            sig: &G1Projective,
-            data: &[(PublicKey, B)],
-        ) -> Choice {
-            use bls12_381_plus::{multi_miller_loop, G2Prepared};
+        crate::Error::verified(self.verify_prehashed(data))
+    }
+
+    /// Prepare the hashed messages and keys and compute the miller loops across threads
+    #[cfg(feature = "parallel")]
+    fn par_aggregate_verify(&self, data: &[(&PublicKeyOf<S>, S::SignatureGroup)]) -> Choice {
+        use rayon::prelude::*;
+
+        if data.iter().any(|(k, _)| k.is_invalid().unwrap_u8() == 1) {
+            return Choice::from(0u8);
+        }
+        let pairs = data
+            .par_iter()
+            .map(|(key, a)| (S::signature_input(a), S::public_key_input(&key.0)))
+            .collect::<Vec<(S::SignatureInput, S::PublicKeyInput)>>();
+        let res = crate::parallel::multi_miller_loop::<S, _>(&pairs, |(p1, p2)| (p1, p2));
+        let sig = S::signature_input(&self.0);
+        (res + S::multi_miller_loop(&[(&sig, &S::neg_generator())]))
+            .final_exponentiation()
+            .is_identity()
+    }
 
-            if data.iter().any(|(k, _)| k.is_invalid().unwrap_u8() == 1) {
+    #[cfg(not(feature = "alloc"))]
+    fn core_aggregate_verify<'a>(
+        &self,
+        data: impl Iterator<Item = (&'a PublicKeyOf<S>, S::SignatureGroup)>,
+    ) -> Choice {
+        let sig = S::signature_input(&self.0);
+        let mut res = S::multi_miller_loop(&[(&sig, &S::neg_generator())]);
+        for (key, a) in data {
+            if key.is_invalid().unwrap_u8() == 1 {
                 return Choice::from(0u8);
             }
+            res = res
+                + S::multi_miller_loop(&[(&S::signature_input(&a), &S::public_key_input(&key.0))]);
+        }
+        res.final_exponentiation().is_identity()
+    }
 
-            let mut data = data
-                .iter()
-                .map(|(key, m)| {
-                    (
-                        Signature::hash_msg(m.as_ref()).to_affine(),
-                        G2Prepared::from(key.0.to_affine()),
-                    )
-                })
-                .collect::<Vec<(G1Affine, G2Prepared)>>();
-
-            data.push((sig.to_affine(), G2Prepared::from(-G2Affine::generator())));
-            // appease borrow checker
-            let t = data
+    #[cfg(all(feature = "alloc", not(feature = "parallel")))]
+    fn core_aggregate_verify<'a>(
+        &self,
+        data: impl Iterator<Item = (&'a PublicKeyOf<S>, S::SignatureGroup)>,
+    ) -> Choice {
+        let pairs = data
+            .map(|(key, a)| {
+                if key.is_invalid().unwrap_u8() == 1 {
+                    return None;
+                }
+                Some((S::signature_input(&a), S::public_key_input(&key.0)))
+            })
+            .collect::<Option<Vec<(S::SignatureInput, S::PublicKeyInput)>>>();
+        let mut data = match pairs {
+            Some(d) => d,
+            None => return Choice::from(0u8),
+        };
+
+        data.push((S::signature_input(&self.0), S::neg_generator()));
+        // appease borrow checker
+        let t = data
+            .iter()
+            .map(|(p1, p2)| (p1, p2))
+            .collect::<Vec<(&S::SignatureInput, &S::PublicKeyInput)>>();
+        S::pairing_check(t.as_slice())
+    }
+
+    /// Verify this aggregate signature is over each `msg` with the prepared public keys
+    pub fn verify_prepared<B: AsRef<[u8]>>(&self, data: &[(&PreparedPublicKeyOf<S>, B)]) -> Choice {
+        if self.is_invalid().unwrap_u8() == 1 || data.is_empty() {
+            return Choice::from(0u8);
+        }
+        let sig = S::signature_input(&self.0);
+
+        #[cfg(not(feature = "alloc"))]
+        {
+            let mut res = S::with_neg_generator(|g| S::multi_miller_loop(&[(&sig, g)]));
+            for (key, msg) in data {
+                let a = S::signature_input(&SignatureOf::<S>::hash_msg(msg.as_ref()));
+                res = res + S::multi_miller_loop(&[(&a, &key.prepared)]);
+            }
+            res.final_exponentiation().is_identity()
+        }
+        #[cfg(feature = "alloc")]
+        {
+            let hashes = data
                 .iter()
-                .map(|(p1, p2)| (p1, p2))
-                .collect::<Vec<(&G1Affine, &G2Prepared)>>();
-            multi_miller_loop(t.as_slice())
-                .final_exponentiation()
-                .is_identity()
+                .map(|(_, m)| S::signature_input(&SignatureOf::<S>::hash_msg(m.as_ref())))
+                .collect::<Vec<S::SignatureInput>>();
+            S::with_neg_generator(|neg_generator| {
+                let mut t = hashes
+                    .iter()
+                    .zip(data.iter())
+                    .map(|(a, (key, _))| (a, &key.prepared))
+                    .collect::<Vec<(&S::SignatureInput, &S::PublicKeyInput)>>();
+                t.push((&sig, neg_generator));
+                S::pairing_check(t.as_slice())
+            })
+        }
+    }
+
+    /// Verify this aggregate signature is over each `msg` with the prepared public keys
+    /// and return the reason if it isn't
+    pub fn try_verify_prepared<B: AsRef<[u8]>>(
+        &self,
+        data: &[(&PreparedPublicKeyOf<S>, B)],
+    ) -> Result<(), crate::Error> {
+        self.validate()?;
+        if data.is_empty() {
+            return Err(crate::Error::Empty);
+        }
+        crate::Error::verified(self.verify_prepared(data))
+    }
+
+    /// Create a zero-knowledge proof of a valid aggregate signature
+    /// `msgs` are the messages of each signature in the aggregate in order
+    /// `x` should be a random Scalar and kept private
+    /// `y` is generated by the verifier
+    #[cfg(feature = "alloc")]
+    pub fn proof_of_knowledge<B: AsRef<[u8]>>(
+        &self,
+        msgs: &[B],
+        x: bls12_381_plus::Scalar,
+        y: bls12_381_plus::Scalar,
+    ) -> Option<crate::AggregateProofOfKnowledgeOf<S>> {
+        self.try_proof_of_knowledge(msgs, x, y).ok()
+    }
+
+    /// Create a zero-knowledge proof of a valid aggregate signature
+    /// and return the reason if it fails
+    #[cfg(feature = "alloc")]
+    pub fn try_proof_of_knowledge<B: AsRef<[u8]>>(
+        &self,
+        msgs: &[B],
+        x: bls12_381_plus::Scalar,
+        y: bls12_381_plus::Scalar,
+    ) -> Result<crate::AggregateProofOfKnowledgeOf<S>, crate::Error> {
+        use ff::Field;
+
+        self.validate()?;
+        if msgs.is_empty() {
+            return Err(crate::Error::Empty);
+        }
+        if (x.is_zero() | y.is_zero()).unwrap_u8() == 1u8 {
+            return Err(crate::Error::ZeroScalar);
+        }
+        let mut u = alloc::vec::Vec::with_capacity(msgs.len());
+        for m in msgs {
+            let a = SignatureOf::<S>::hash_msg(m.as_ref());
+            let ua = a * x;
+            if ua.is_identity().unwrap_u8() == 1u8 {
+                return Err(crate::Error::IdentityPoint);
+            }
+            u.push(ua);
+        }
+        let v = self.0 * (x + y);
+        if v.is_identity().unwrap_u8() == 1u8 {
+            return Err(crate::Error::IdentityPoint);
         }
-        core_aggregate_verify(&self.0, data)
+        Ok(crate::AggregateProofOfKnowledgeOf { u, v: -v })
     }
 }
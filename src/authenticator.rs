@@ -0,0 +1,273 @@
+use crate::{Clock, Point, ProofOfKnowledgeOf, ProofOfKnowledgeTimestampOf, PublicKeyOf, Scheme};
+use alloc::collections::BTreeMap;
+use bls12_381_plus::Scalar;
+use ff::Field;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use subtle::Choice;
+
+/// Storage for issued challenges and already seen proofs.
+/// Entries are keyed by a 32 byte digest and carry an expiry
+/// in milliseconds since the unix epoch.
+pub trait NonceStore {
+    /// Record `key` until `expires_ms`.
+    /// Returns false if `key` is already present.
+    fn insert(&mut self, key: [u8; 32], expires_ms: i64) -> bool;
+    /// Remove `key` and return its expiry if it was present
+    fn take(&mut self, key: &[u8; 32]) -> Option<i64>;
+    /// Remove all entries that expired before `now_ms`
+    fn prune(&mut self, now_ms: i64);
+}
+
+/// A [`NonceStore`] held in memory
+#[derive(Clone, Debug, Default)]
+pub struct MemoryNonceStore(BTreeMap<[u8; 32], i64>);
+
+impl MemoryNonceStore {
+    /// The number of entries currently held
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is the store empty
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl NonceStore for MemoryNonceStore {
+    fn insert(&mut self, key: [u8; 32], expires_ms: i64) -> bool {
+        if self.0.contains_key(&key) {
+            return false;
+        }
+        self.0.insert(key, expires_ms);
+        true
+    }
+
+    fn take(&mut self, key: &[u8; 32]) -> Option<i64> {
+        self.0.remove(key)
+    }
+
+    fn prune(&mut self, now_ms: i64) {
+        self.0.retain(|_, expires_ms| *expires_ms >= now_ms);
+    }
+}
+
+/// A challenge issued to a client that must be answered
+/// with a [`ProofOfKnowledgeOf`] before `expires_ms`
+#[derive(Clone, Copy, Debug, Deserialize, Serialize)]
+pub struct Challenge {
+    /// The verifier chosen value to pass to `Signature::proof_of_knowledge`
+    pub y: Scalar,
+    /// When this challenge expires in milliseconds since the unix epoch
+    pub expires_ms: i64,
+}
+
+/// Server side challenge/response authentication using signature proofs of knowledge.
+///
+/// Challenges are bound to a session, expire after a timeout
+/// and can only be answered once. Timestamp based proofs are
+/// remembered for as long as they are valid so they can't be replayed.
+#[derive(Clone, Debug)]
+pub struct Authenticator<N: NonceStore, C: Clock> {
+    store: N,
+    clock: C,
+    timeout_ms: i64,
+}
+
+impl<N: NonceStore, C: Clock> Authenticator<N, C> {
+    // Challenges are issued before the scheme of the answer is known
+    // and timestamp keys include the proof's point so neither names a group
+    const CHALLENGE_DST: &'static [u8] = b"BLS12381-AUTHENTICATOR-CHALLENGE-";
+    const TIMESTAMP_DST: &'static [u8] = b"BLS12381-AUTHENTICATOR-TIMESTAMP-";
+
+    /// Create a new authenticator where challenges and
+    /// timestamp proofs are valid for `timeout_ms`.
+    /// Returns `None` if `timeout_ms` isn't positive.
+    pub fn new(store: N, clock: C, timeout_ms: i64) -> Option<Self> {
+        if timeout_ms <= 0 {
+            return None;
+        }
+        Some(Self {
+            store,
+            clock,
+            timeout_ms,
+        })
+    }
+
+    /// The underlying nonce store
+    pub fn store(&self) -> &N {
+        &self.store
+    }
+
+    /// Issue a new challenge for `session`
+    pub fn issue<B: AsRef<[u8]>>(
+        &mut self,
+        session: B,
+        mut rng: impl RngCore + CryptoRng,
+    ) -> Challenge {
+        let now = self.clock.now_ms();
+        self.store.prune(now);
+        let expires_ms = now.saturating_add(self.timeout_ms);
+        loop {
+            let y = Scalar::random(&mut rng);
+            if y.is_zero().unwrap_u8() == 1u8 {
+                continue;
+            }
+            if self
+                .store
+                .insert(Self::challenge_key(session.as_ref(), &y), expires_ms)
+            {
+                return Challenge { y, expires_ms };
+            }
+        }
+    }
+
+    /// Verify a proof answering the challenge `y` issued for `session`.
+    /// The challenge is consumed whether or not the proof is valid.
+    pub fn verify<S: Scheme, B: AsRef<[u8]>, M: AsRef<[u8]>>(
+        &mut self,
+        session: B,
+        y: Scalar,
+        proof: &ProofOfKnowledgeOf<S>,
+        pk: PublicKeyOf<S>,
+        msg: M,
+    ) -> Choice {
+        let now = self.clock.now_ms();
+        let expires_ms = match self.store.take(&Self::challenge_key(session.as_ref(), &y)) {
+            Some(e) => e,
+            None => return 0u8.into(),
+        };
+        self.store.prune(now);
+        if now > expires_ms {
+            return 0u8.into();
+        }
+        proof.verify(pk, msg, y)
+    }
+
+    /// Verify a timestamp based proof allowing for `skew_ms` of clock drift.
+    /// A proof is rejected if it has already been accepted.
+    pub fn verify_timestamp<S: Scheme, M: AsRef<[u8]>>(
+        &mut self,
+        proof: &ProofOfKnowledgeTimestampOf<S>,
+        pk: PublicKeyOf<S>,
+        msg: M,
+        skew_ms: i64,
+    ) -> Choice {
+        let now = self.clock.now_ms();
+        self.store.prune(now);
+        let res = proof.verify_with_clock(pk, msg, self.timeout_ms, skew_ms, &self.clock);
+        if res.unwrap_u8() == 0u8 {
+            return res;
+        }
+        // Remember the proof until it can no longer pass the timeout check
+        let expires_ms = proof
+            .t
+            .saturating_add(self.timeout_ms)
+            .saturating_add(skew_ms);
+        Choice::from(self.store.insert(Self::timestamp_key(proof), expires_ms) as u8)
+    }
+
+    fn challenge_key(session: &[u8], y: &Scalar) -> [u8; 32] {
+        Sha256::new()
+            .chain(Self::CHALLENGE_DST)
+            .chain((session.len() as u64).to_le_bytes())
+            .chain(session)
+            .chain(y.to_bytes())
+            .finalize()
+            .into()
+    }
+
+    fn timestamp_key<S: Scheme>(proof: &ProofOfKnowledgeTimestampOf<S>) -> [u8; 32] {
+        Sha256::new()
+            .chain(Self::TIMESTAMP_DST)
+            .chain(Point::to_compressed(&proof.pok.u))
+            .chain(proof.t.to_le_bytes())
+            .finalize()
+            .into()
+    }
+}
+
+#[test]
+fn authenticator_works() {
+    use crate::{MockRng, PublicKey, PublicKeyVt, SecretKey, Signature, SignatureVt};
+    use core::cell::Cell;
+    use rand_core::SeedableRng;
+
+    struct TestClock(Cell<i64>);
+
+    impl Clock for TestClock {
+        fn now_ms(&self) -> i64 {
+            self.0.get()
+        }
+    }
+
+    let mut rng = MockRng::from_seed([17u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let msg = b"authenticator";
+    let sig = Signature::new(&sk, msg).unwrap();
+
+    let clock = TestClock(Cell::new(1_000));
+    assert!(Authenticator::new(MemoryNonceStore::default(), &clock, 0).is_none());
+    assert!(Authenticator::new(MemoryNonceStore::default(), &clock, -1).is_none());
+    let mut auth = Authenticator::new(MemoryNonceStore::default(), &clock, 500).unwrap();
+
+    let challenge = auth.issue(b"session 1", &mut rng);
+    let proof = sig
+        .proof_of_knowledge(msg, Scalar::random(&mut rng), challenge.y)
+        .unwrap();
+    // Wrong session doesn't consume the challenge
+    assert_eq!(
+        auth.verify(b"session 2", challenge.y, &proof, pk, msg)
+            .unwrap_u8(),
+        0u8
+    );
+    assert_eq!(
+        auth.verify(b"session 1", challenge.y, &proof, pk, msg)
+            .unwrap_u8(),
+        1u8
+    );
+    // Replays are rejected
+    assert_eq!(
+        auth.verify(b"session 1", challenge.y, &proof, pk, msg)
+            .unwrap_u8(),
+        0u8
+    );
+
+    // Expired challenges are rejected
+    let challenge = auth.issue(b"session 1", &mut rng);
+    let proof = sig
+        .proof_of_knowledge(msg, Scalar::random(&mut rng), challenge.y)
+        .unwrap();
+    clock.0.set(1_501);
+    assert_eq!(
+        auth.verify(b"session 1", challenge.y, &proof, pk, msg)
+            .unwrap_u8(),
+        0u8
+    );
+    assert!(auth.store().is_empty());
+
+    let proof = sig
+        .proof_of_knowledge_with_clock(msg, Scalar::random(&mut rng), &clock)
+        .unwrap();
+    assert_eq!(auth.verify_timestamp(&proof, pk, msg, 0).unwrap_u8(), 1u8);
+    assert_eq!(auth.verify_timestamp(&proof, pk, msg, 0).unwrap_u8(), 0u8);
+    clock.0.set(2_002);
+    assert_eq!(auth.verify_timestamp(&proof, pk, msg, 0).unwrap_u8(), 0u8);
+    assert!(auth.store().is_empty());
+
+    // The same authenticator accepts proofs for the Vt types
+    let pk_vt = PublicKeyVt::from(&sk);
+    let sig_vt = SignatureVt::new(&sk, msg).unwrap();
+    let challenge = auth.issue(b"session 1", &mut rng);
+    let proof = sig_vt
+        .proof_of_knowledge(msg, Scalar::random(&mut rng), challenge.y)
+        .unwrap();
+    assert_eq!(
+        auth.verify(b"session 1", challenge.y, &proof, pk_vt, msg)
+            .unwrap_u8(),
+        1u8
+    );
+}
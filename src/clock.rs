@@ -0,0 +1,37 @@
+/// A source of the current time used by timestamp based proofs of knowledge
+pub trait Clock {
+    /// The number of milliseconds since the unix epoch
+    fn now_ms(&self) -> i64;
+}
+
+impl<C: Clock + ?Sized> Clock for &C {
+    fn now_ms(&self) -> i64 {
+        (**self).now_ms()
+    }
+}
+
+/// A clock that reads the system's wall clock in UTC
+#[cfg(feature = "iso8601-timestamp")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "iso8601-timestamp")]
+impl Clock for SystemClock {
+    fn now_ms(&self) -> i64 {
+        iso8601_timestamp::Timestamp::now_utc()
+            .duration_since(iso8601_timestamp::Timestamp::UNIX_EPOCH)
+            .whole_milliseconds() as i64
+    }
+}
+
+/// A clock that always returns the same time.
+/// Useful for deterministic tests or when the time
+/// comes from an external source e.g. a block header.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FixedClock(pub i64);
+
+impl Clock for FixedClock {
+    fn now_ms(&self) -> i64 {
+        self.0
+    }
+}
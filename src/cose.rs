@@ -0,0 +1,287 @@
+use crate::{Error, PublicKey, PublicKeyVt, SecretKey, Signature, SignatureVt};
+use alloc::{boxed::Box, string::String, vec, vec::Vec};
+use ciborium::value::{Integer, Value};
+use zeroize::Zeroizing;
+
+/// COSE_Key `kty` for keys with the compressed point in `x`
+pub const COSE_KTY_OKP: i64 = 1;
+/// COSE_Key `kty` for keys with the affine coordinates in `x` and `y`
+pub const COSE_KTY_EC2: i64 = 2;
+/// COSE_Key `crv` for keys in G1
+pub const COSE_CRV_BLS12381G1: i64 = 13;
+/// COSE_Key `crv` for keys in G2
+pub const COSE_CRV_BLS12381G2: i64 = 14;
+
+const LABEL_KTY: i64 = 1;
+const LABEL_ALG: i64 = 1;
+const LABEL_CRV: i64 = -1;
+const LABEL_X: i64 = -2;
+const LABEL_Y: i64 = -3;
+const LABEL_D: i64 = -4;
+/// The CBOR tag for COSE_Sign1
+const COSE_SIGN1_TAG: u64 = 18;
+
+fn int(i: i64) -> Value {
+    Value::Integer(Integer::from(i))
+}
+
+fn to_cbor(value: &Value) -> Vec<u8> {
+    let mut out = Vec::new();
+    // writing to a vec can't fail so `expect` is okay
+    ciborium::ser::into_writer(value, &mut out).expect("CBOR encoding");
+    out
+}
+
+fn from_cbor(bytes: &[u8]) -> Result<Value, Error> {
+    ciborium::de::from_reader(bytes).map_err(|_| Error::InvalidEncoding)
+}
+
+fn get(map: &[(Value, Value)], label: i64) -> Option<&Value> {
+    map.iter()
+        .find(|(k, _)| k.as_integer().map(i128::from) == Some(label as i128))
+        .map(|(_, v)| v)
+}
+
+fn get_int(map: &[(Value, Value)], label: i64) -> Result<i128, Error> {
+    get(map, label)
+        .and_then(Value::as_integer)
+        .map(i128::from)
+        .ok_or(Error::InvalidEncoding)
+}
+
+fn get_bytes(map: &[(Value, Value)], label: i64) -> Result<&[u8], Error> {
+    get(map, label)
+        .and_then(Value::as_bytes)
+        .map(|b| b.as_slice())
+        .ok_or(Error::InvalidEncoding)
+}
+
+fn cose_key(kty: i64, crv: i64, coords: &[&[u8]]) -> Vec<(Value, Value)> {
+    let mut map = vec![(int(LABEL_KTY), int(kty)), (int(LABEL_CRV), int(crv))];
+    for (label, c) in [LABEL_X, LABEL_Y].iter().zip(coords) {
+        map.push((int(*label), Value::Bytes(c.to_vec())));
+    }
+    map
+}
+
+macro_rules! cose_key_impl {
+    ($public_key:ident, $crv:expr, $coord:expr) => {
+        impl $public_key {
+            /// Get the COSE_Key with `kty: OKP` and the compressed point
+            pub fn to_cose_key(&self) -> Vec<u8> {
+                to_cbor(&Value::Map(cose_key(
+                    COSE_KTY_OKP,
+                    $crv,
+                    &[&self.to_bytes()],
+                )))
+            }
+
+            /// Get the COSE_Key with `kty: EC2` and the affine coordinates
+            pub fn to_cose_key_ec2(&self) -> Vec<u8> {
+                let bytes = self.to_uncompressed_bytes();
+                let (x, y) = bytes.split_at($coord);
+                to_cbor(&Value::Map(cose_key(COSE_KTY_EC2, $crv, &[x, y])))
+            }
+
+            /// Convert a COSE_Key in either form, ignoring any secret key
+            pub fn from_cose_key(bytes: &[u8]) -> Result<Self, Error> {
+                let key = from_cbor(bytes)?;
+                Self::from_cose_map(key.as_map().ok_or(Error::InvalidEncoding)?)
+            }
+
+            fn from_cose_map(map: &[(Value, Value)]) -> Result<Self, Error> {
+                if get_int(map, LABEL_CRV)? != $crv as i128 {
+                    return Err(Error::InvalidEncoding);
+                }
+                let x = get_bytes(map, LABEL_X)?;
+                let kty = get_int(map, LABEL_KTY)?;
+                if kty == COSE_KTY_OKP as i128 && get(map, LABEL_Y).is_none() {
+                    Self::try_from_bytes(x)
+                } else if kty == COSE_KTY_EC2 as i128 {
+                    if x.len() != $coord {
+                        return Err(Error::InvalidLength {
+                            expected: $coord,
+                            actual: x.len(),
+                        });
+                    }
+                    let mut bytes = x.to_vec();
+                    bytes.extend_from_slice(get_bytes(map, LABEL_Y)?);
+                    Self::try_from_uncompressed_bytes(&bytes)
+                } else {
+                    Err(Error::InvalidEncoding)
+                }
+            }
+        }
+    };
+}
+
+cose_key_impl!(PublicKey, COSE_CRV_BLS12381G2, 96);
+cose_key_impl!(PublicKeyVt, COSE_CRV_BLS12381G1, 48);
+
+impl SecretKey {
+    /// Get the COSE_Key with the public key in G2 for signing with [`Signature`]
+    pub fn to_cose_key(&self) -> Zeroizing<Vec<u8>> {
+        let pk = PublicKey::from(self).to_bytes();
+        self.cose_key(COSE_CRV_BLS12381G2, &pk)
+    }
+
+    /// Get the COSE_Key with the public key in G1 for signing with [`SignatureVt`]
+    pub fn to_cose_key_vt(&self) -> Zeroizing<Vec<u8>> {
+        let pk = PublicKeyVt::from(self).to_bytes();
+        self.cose_key(COSE_CRV_BLS12381G1, &pk)
+    }
+
+    fn cose_key(&self, crv: i64, pk: &[u8]) -> Zeroizing<Vec<u8>> {
+        let mut map = cose_key(COSE_KTY_OKP, crv, &[pk]);
+        map.push((int(LABEL_D), Value::Bytes(self.to_bytes().to_vec())));
+        Zeroizing::new(to_cbor(&Value::Map(map)))
+    }
+
+    /// Convert a COSE_Key with a secret key and check it matches the public key
+    pub fn from_cose_key(bytes: &[u8]) -> Result<Self, Error> {
+        let key = from_cbor(bytes)?;
+        let map = key.as_map().ok_or(Error::InvalidEncoding)?;
+        let sk = Self::try_from_bytes(get_bytes(map, LABEL_D)?)?;
+        let crv = get_int(map, LABEL_CRV)?;
+        let matches = if crv == COSE_CRV_BLS12381G2 as i128 {
+            PublicKey::from_cose_map(map)? == PublicKey::from(&sk)
+        } else if crv == COSE_CRV_BLS12381G1 as i128 {
+            PublicKeyVt::from_cose_map(map)? == PublicKeyVt::from(&sk)
+        } else {
+            false
+        };
+        if !matches {
+            return Err(Error::InvalidEncoding);
+        }
+        Ok(sk)
+    }
+}
+
+/// The encoded protected header with only the algorithm
+fn protected_header(alg: i64) -> Vec<u8> {
+    to_cbor(&Value::Map(vec![(int(LABEL_ALG), int(alg))]))
+}
+
+/// The Sig_structure that is signed for COSE_Sign1
+fn sig_structure(protected: &[u8], aad: &[u8], payload: &[u8]) -> Vec<u8> {
+    to_cbor(&Value::Array(vec![
+        Value::Text(String::from("Signature1")),
+        Value::Bytes(protected.to_vec()),
+        Value::Bytes(aad.to_vec()),
+        Value::Bytes(payload.to_vec()),
+    ]))
+}
+
+/// Split a COSE_Sign1 into the protected header, payload and signature
+fn sign1_parts(bytes: &[u8], alg: i64) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>), Error> {
+    let value = match from_cbor(bytes)? {
+        Value::Tag(COSE_SIGN1_TAG, inner) => *inner,
+        Value::Tag(..) => return Err(Error::InvalidEncoding),
+        value => value,
+    };
+    let parts = value.into_array().map_err(|_| Error::InvalidEncoding)?;
+    let [protected, unprotected, payload, signature] =
+        <[Value; 4]>::try_from(parts).map_err(|_| Error::InvalidEncoding)?;
+    // Nothing in the unprotected header is used
+    if !unprotected.is_map() {
+        return Err(Error::InvalidEncoding);
+    }
+    let bytes = |v: Value| v.into_bytes().map_err(|_| Error::InvalidEncoding);
+    let (protected, payload, signature) = (bytes(protected)?, bytes(payload)?, bytes(signature)?);
+    let header = from_cbor(&protected)?;
+    let header = header.as_map().ok_or(Error::InvalidEncoding)?;
+    if get_int(header, LABEL_ALG)? != alg as i128 {
+        return Err(Error::InvalidEncoding);
+    }
+    Ok((protected, payload, signature))
+}
+
+macro_rules! cose_sign1_impl {
+    ($signature:ident, $public_key:ident, $alg:expr) => {
+        impl $signature {
+            /// The COSE `alg` for this signature.
+            /// There is no registered value yet so this is from the private use range.
+            pub const COSE_ALG: i64 = $alg;
+
+            /// Sign `payload` with the externally supplied `aad`
+            /// and return the tagged COSE_Sign1
+            pub fn sign_cose(sk: &SecretKey, payload: &[u8], aad: &[u8]) -> Option<Vec<u8>> {
+                let protected = protected_header(Self::COSE_ALG);
+                let sig = Self::new(sk, sig_structure(&protected, aad, payload))?;
+                Some(to_cbor(&Value::Tag(
+                    COSE_SIGN1_TAG,
+                    Box::new(Value::Array(vec![
+                        Value::Bytes(protected),
+                        Value::Map(Vec::new()),
+                        Value::Bytes(payload.to_vec()),
+                        Value::Bytes(sig.to_bytes().to_vec()),
+                    ])),
+                )))
+            }
+
+            /// Verify a tagged or untagged COSE_Sign1 with the externally
+            /// supplied `aad` and return the payload
+            pub fn verify_cose(
+                pk: $public_key,
+                bytes: &[u8],
+                aad: &[u8],
+            ) -> Result<Vec<u8>, Error> {
+                let (protected, payload, sig) = sign1_parts(bytes, Self::COSE_ALG)?;
+                Self::try_from_bytes(&sig)?
+                    .try_verify(pk, sig_structure(&protected, aad, &payload))?;
+                Ok(payload)
+            }
+        }
+    };
+}
+
+cose_sign1_impl!(Signature, PublicKey, -65537);
+cose_sign1_impl!(SignatureVt, PublicKeyVt, -65538);
+
+#[test]
+fn cose_works() {
+    use crate::MockRng;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([79u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let pk_vt = PublicKeyVt::from(&sk);
+
+    let key = pk.to_cose_key();
+    assert_eq!(PublicKey::from_cose_key(&key).unwrap(), pk);
+    assert!(PublicKeyVt::from_cose_key(&key).is_err());
+    assert_eq!(PublicKey::from_cose_key(&pk.to_cose_key_ec2()).unwrap(), pk);
+    assert_eq!(
+        PublicKeyVt::from_cose_key(&pk_vt.to_cose_key_ec2()).unwrap(),
+        pk_vt
+    );
+    assert_eq!(
+        PublicKeyVt::from_cose_key(&pk_vt.to_cose_key()).unwrap(),
+        pk_vt
+    );
+
+    let key = sk.to_cose_key();
+    assert_eq!(SecretKey::from_cose_key(&key).unwrap(), sk);
+    assert_eq!(PublicKey::from_cose_key(&key).unwrap(), pk);
+    assert_eq!(SecretKey::from_cose_key(&sk.to_cose_key_vt()).unwrap(), sk);
+    assert!(SecretKey::from_cose_key(&pk.to_cose_key()).is_err());
+
+    let payload = b"payload";
+    let msg = Signature::sign_cose(&sk, payload, b"aad").unwrap();
+    assert_eq!(Signature::verify_cose(pk, &msg, b"aad").unwrap(), payload);
+    assert!(Signature::verify_cose(pk, &msg, b"other aad").is_err());
+    assert!(SignatureVt::verify_cose(pk_vt, &msg, b"aad").is_err());
+    // Untagged messages are accepted too
+    let untagged = match from_cbor(&msg).unwrap() {
+        Value::Tag(_, inner) => to_cbor(&inner),
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        Signature::verify_cose(pk, &untagged, b"aad").unwrap(),
+        payload
+    );
+
+    let msg = SignatureVt::sign_cose(&sk, payload, &[]).unwrap();
+    assert_eq!(SignatureVt::verify_cose(pk_vt, &msg, &[]).unwrap(), payload);
+}
@@ -0,0 +1,146 @@
+use crate::{Error, Multikey, PublicKey, PublicKeyVt};
+use alloc::{format, string::String, vec, vec::Vec};
+use multibase::Base;
+use serde::{Deserialize, Serialize};
+
+/// The prefix of every did:key identifier
+const DID_KEY_PREFIX: &str = "did:key:";
+
+/// A public key that can be used as a `did:key` identifier.
+/// See <https://w3c-ccg.github.io/did-method-key/>
+pub trait DidKey: Multikey {
+    /// Get the `did:key` identifier, the base58btc multibase multikey
+    fn to_did_key(&self) -> String {
+        format!("{}{}", DID_KEY_PREFIX, self.to_multibase(Base::Base58Btc))
+    }
+
+    /// Convert a `did:key` identifier, with or without a fragment
+    fn from_did_key(did: &str) -> Result<Self, Error> {
+        let id = did.strip_prefix(DID_KEY_PREFIX).ok_or(Error::InvalidDid)?;
+        let id = id.split_once('#').map_or(id, |(id, _)| id);
+        // did:key only allows base58btc
+        if !id.starts_with('z') {
+            return Err(Error::InvalidDid);
+        }
+        Self::from_multibase(id)
+    }
+
+    /// Generate the DID document for this key with a single Multikey verification method
+    fn to_did_document(&self) -> DidDocument {
+        let multibase = self.to_multibase(Base::Base58Btc);
+        let did = format!("{}{}", DID_KEY_PREFIX, multibase);
+        let method_id = format!("{}#{}", did, multibase);
+        DidDocument {
+            context: vec![
+                String::from("https://www.w3.org/ns/did/v1"),
+                String::from("https://w3id.org/security/multikey/v1"),
+            ],
+            verification_method: vec![VerificationMethod {
+                id: method_id.clone(),
+                type_: String::from("Multikey"),
+                controller: did.clone(),
+                public_key_multibase: multibase,
+            }],
+            authentication: vec![method_id.clone()],
+            assertion_method: vec![method_id.clone()],
+            capability_delegation: vec![method_id.clone()],
+            capability_invocation: vec![method_id],
+            id: did,
+        }
+    }
+}
+
+impl DidKey for PublicKey {}
+
+impl DidKey for PublicKeyVt {}
+
+/// A DID document for a `did:key` identifier
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DidDocument {
+    /// The JSON-LD contexts
+    #[serde(rename = "@context")]
+    pub context: Vec<String>,
+    /// The DID
+    pub id: String,
+    /// The keys for this DID
+    pub verification_method: Vec<VerificationMethod>,
+    /// The verification methods that can authenticate as the DID
+    pub authentication: Vec<String>,
+    /// The verification methods that can issue credentials
+    pub assertion_method: Vec<String>,
+    /// The verification methods that can delegate capabilities
+    pub capability_delegation: Vec<String>,
+    /// The verification methods that can invoke capabilities
+    pub capability_invocation: Vec<String>,
+}
+
+impl DidDocument {
+    /// Get the public key from the first verification method
+    pub fn public_key<K: DidKey>(&self) -> Result<K, Error> {
+        let method = self.verification_method.first().ok_or(Error::InvalidDid)?;
+        if method.type_ != "Multikey" || method.controller != self.id {
+            return Err(Error::InvalidDid);
+        }
+        let key = K::from_multibase(&method.public_key_multibase)?;
+        if key.to_did_key() != self.id {
+            return Err(Error::InvalidDid);
+        }
+        Ok(key)
+    }
+}
+
+/// A Multikey verification method in a DID document
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerificationMethod {
+    /// The DID URL for this method
+    pub id: String,
+    /// Always `Multikey`
+    #[serde(rename = "type")]
+    pub type_: String,
+    /// The DID that controls this key
+    pub controller: String,
+    /// The multibase multikey
+    pub public_key_multibase: String,
+}
+
+#[test]
+fn did_key_works() {
+    use crate::{MockRng, SecretKey};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([71u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let pk_vt = PublicKeyVt::from(&sk);
+
+    let did = pk.to_did_key();
+    assert!(did.starts_with("did:key:zUC7"));
+    assert_eq!(PublicKey::from_did_key(&did).unwrap(), pk);
+    let did_vt = pk_vt.to_did_key();
+    assert!(did_vt.starts_with("did:key:z3tEF"));
+    assert_eq!(PublicKeyVt::from_did_key(&did_vt).unwrap(), pk_vt);
+    assert!(PublicKey::from_did_key(&did_vt).is_err());
+    assert!(PublicKey::from_did_key(&did[4..]).is_err());
+    assert!(
+        PublicKey::from_did_key(&format!("did:key:{}", pk.to_multibase(Base::Base64))).is_err()
+    );
+
+    let doc = pk.to_did_document();
+    assert_eq!(doc.id, did);
+    let method = &doc.verification_method[0];
+    assert_eq!(method.id, format!("{}#{}", did, &did[8..]));
+    assert_eq!(PublicKey::from_did_key(&method.id).unwrap(), pk);
+    assert_eq!(doc.public_key::<PublicKey>().unwrap(), pk);
+    assert!(doc.public_key::<PublicKeyVt>().is_err());
+
+    let json = serde_json::to_value(&doc).unwrap();
+    assert_eq!(json["@context"][1], "https://w3id.org/security/multikey/v1");
+    assert_eq!(json["verificationMethod"][0]["type"], "Multikey");
+    assert_eq!(
+        json["verificationMethod"][0]["publicKeyMultibase"],
+        &did[8..]
+    );
+    assert_eq!(serde_json::from_value::<DidDocument>(json).unwrap(), doc);
+}
@@ -0,0 +1,283 @@
+use core::fmt::{self, Display, Formatter};
+use subtle::Choice;
+
+/// The reasons an operation can fail
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The secret key is zero
+    ZeroKey,
+    /// The point is the identity element
+    IdentityPoint,
+    /// The point is not on the curve
+    NotOnCurve,
+    /// The point is not in the prime order subgroup
+    NotInSubgroup,
+    /// The input is not the expected length
+    InvalidLength {
+        /// The expected number of bytes
+        expected: usize,
+        /// The number of bytes received
+        actual: usize,
+    },
+    /// The bytes are not a valid encoding
+    InvalidEncoding,
+    /// Splitting or combining shares failed
+    Sharing(vsss_rs::Error),
+    /// The message was hashed with a different domain separation tag
+    InvalidDst,
+    /// The pairing check failed
+    VerificationFailed,
+    /// The multicodec is not the one expected for the type
+    InvalidCodec {
+        /// The expected multicodec
+        expected: u64,
+        /// The multicodec received
+        actual: u64,
+    },
+    /// The multicodec is not a BLS12-381 key or signature
+    UnknownCodec(u64),
+    /// The DID or DID document is not a valid did:key for the key type
+    InvalidDid,
+    /// A scalar that must be non-zero is zero
+    ZeroScalar,
+    /// The timestamp is outside the accepted window
+    InvalidTimestamp,
+    /// No keys, signatures or messages were supplied
+    Empty,
+    /// The index is out of range for the key set
+    InvalidIndex(usize),
+    /// The index is already present
+    DuplicateIndex(usize),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroKey => write!(f, "secret key is zero"),
+            Self::IdentityPoint => write!(f, "point is the identity"),
+            Self::NotOnCurve => write!(f, "point is not on the curve"),
+            Self::NotInSubgroup => write!(f, "point is not in the prime order subgroup"),
+            Self::InvalidLength { expected, actual } => {
+                write!(
+                    f,
+                    "invalid length, expected {} bytes, found {}",
+                    expected, actual
+                )
+            }
+            Self::InvalidEncoding => write!(f, "invalid encoding"),
+            Self::Sharing(e) => write!(f, "secret sharing failed: {:?}", e),
+            Self::InvalidDst => write!(f, "message hashed with a different domain separation tag"),
+            Self::VerificationFailed => write!(f, "verification failed"),
+            Self::InvalidCodec { expected, actual } => {
+                write!(
+                    f,
+                    "invalid multicodec, expected {:#x}, found {:#x}",
+                    expected, actual
+                )
+            }
+            Self::UnknownCodec(c) => write!(f, "unknown multicodec {:#x}", c),
+            Self::InvalidDid => write!(f, "invalid did:key"),
+            Self::ZeroScalar => write!(f, "scalar is zero"),
+            Self::InvalidTimestamp => write!(f, "timestamp is outside the accepted window"),
+            Self::Empty => write!(f, "no inputs"),
+            Self::InvalidIndex(i) => write!(f, "index {} is out of range", i),
+            Self::DuplicateIndex(i) => write!(f, "index {} is already present", i),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+impl From<vsss_rs::Error> for Error {
+    fn from(e: vsss_rs::Error) -> Self {
+        Self::Sharing(e)
+    }
+}
+
+impl From<Error> for signature::Error {
+    fn from(e: Error) -> Self {
+        #[cfg(feature = "std")]
+        {
+            Self::from_source(e)
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = e;
+            Self::new()
+        }
+    }
+}
+
+impl Error {
+    /// Map the result of a pairing check
+    pub(crate) fn verified(res: Choice) -> Result<(), Self> {
+        if res.unwrap_u8() == 1u8 {
+            Ok(())
+        } else {
+            Err(Self::VerificationFailed)
+        }
+    }
+
+    /// Check a point is not the identity, is on the curve and in the subgroup
+    pub(crate) fn check_point(
+        is_identity: Choice,
+        is_on_curve: Choice,
+        is_torsion_free: Choice,
+    ) -> Result<(), Self> {
+        if is_identity.unwrap_u8() == 1u8 {
+            return Err(Self::IdentityPoint);
+        }
+        if is_on_curve.unwrap_u8() == 0u8 {
+            return Err(Self::NotOnCurve);
+        }
+        if is_torsion_free.unwrap_u8() == 0u8 {
+            return Err(Self::NotInSubgroup);
+        }
+        Ok(())
+    }
+
+    /// Check `bytes` is exactly `N` bytes long
+    pub(crate) fn check_length<const N: usize>(bytes: &[u8]) -> Result<&[u8; N], Self> {
+        <&[u8; N]>::try_from(bytes).map_err(|_| Self::InvalidLength {
+            expected: N,
+            actual: bytes.len(),
+        })
+    }
+
+    /// Check `bytes` is exactly `expected` bytes long and convert it to `B`
+    pub(crate) fn check_bytes<B: for<'a> TryFrom<&'a [u8]>>(
+        bytes: &[u8],
+        expected: usize,
+    ) -> Result<B, Self> {
+        B::try_from(bytes).map_err(|_| Self::InvalidLength {
+            expected,
+            actual: bytes.len(),
+        })
+    }
+}
+
+#[test]
+fn error_works() {
+    use crate::*;
+    use bls12_381_plus::Scalar;
+    use ff::Field;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([53u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let msg = b"error";
+
+    assert_eq!(
+        Signature::try_new(&SecretKey::default(), msg),
+        Err(Error::ZeroKey)
+    );
+    let sig = Signature::try_new(&sk, msg).unwrap();
+    assert!(sig.try_verify(pk, msg).is_ok());
+    assert_eq!(
+        sig.try_verify(pk, b"another message"),
+        Err(Error::VerificationFailed)
+    );
+    assert_eq!(
+        sig.try_verify(PublicKey::default(), msg),
+        Err(Error::IdentityPoint)
+    );
+    let prepared = PreparedPublicKey::from(Validated::new(pk).unwrap());
+    assert!(sig.try_verify_prepared(&prepared, msg).is_ok());
+    assert_eq!(
+        sig.try_verify_prepared(&prepared, b"another message"),
+        Err(Error::VerificationFailed)
+    );
+
+    let bytes = sig.to_bytes();
+    assert_eq!(Signature::try_from_bytes(&bytes).unwrap(), sig);
+    assert_eq!(
+        Signature::try_from_bytes(&bytes[1..]),
+        Err(Error::InvalidLength {
+            expected: 48,
+            actual: 47
+        })
+    );
+    assert_eq!(
+        Signature::try_from_bytes(&[0xffu8; 48]),
+        Err(Error::InvalidEncoding)
+    );
+    assert_eq!(
+        Signature::try_from_bytes(&Signature::default().to_bytes()),
+        Err(Error::IdentityPoint)
+    );
+    assert_eq!(SecretKey::try_from_bytes(&[0u8; 32]), Err(Error::ZeroKey));
+    assert_eq!(
+        SecretKey::try_hash(b"error").unwrap(),
+        SecretKey::hash(b"error").unwrap()
+    );
+
+    let pop = ProofOfPossession::try_new(&sk).unwrap();
+    assert!(pop.try_verify(pk).is_ok());
+    assert!(pop.try_verify_prepared(&prepared).is_ok());
+    assert!(matches!(
+        Signature::try_from_partials::<2, 3>(&[]),
+        Err(Error::Sharing(_))
+    ));
+
+    let x = Scalar::random(&mut rng);
+    let y = Scalar::random(&mut rng);
+    assert_eq!(
+        sig.try_proof_of_knowledge(msg, Scalar::zero(), y),
+        Err(Error::ZeroScalar)
+    );
+    let proof = sig.try_proof_of_knowledge(msg, x, y).unwrap();
+    assert!(proof.try_verify_prepared(&prepared, msg, y).is_ok());
+    assert_eq!(
+        proof.try_verify_prepared(&prepared, msg, Scalar::zero()),
+        Err(Error::ZeroScalar)
+    );
+    let proof = sig
+        .try_proof_of_knowledge_with_context(pk, msg, b"ctx", x)
+        .unwrap();
+    assert!(proof.try_verify_with_context(pk, msg, b"ctx").is_ok());
+    assert_eq!(
+        proof.try_verify_with_context(pk, msg, b"other"),
+        Err(Error::VerificationFailed)
+    );
+    let clock = FixedClock(1_000_000);
+    let proof = sig
+        .try_proof_of_knowledge_with_clock(msg, x, &clock)
+        .unwrap();
+    assert!(proof
+        .try_verify_with_clock(pk, msg, 1_000, 0, &clock)
+        .is_ok());
+    assert_eq!(
+        proof.try_verify_with_clock(pk, msg, 1_000, 0, &FixedClock(1_002_000)),
+        Err(Error::InvalidTimestamp)
+    );
+
+    #[cfg(feature = "alloc")]
+    {
+        let sk2 = SecretKey::random(&mut rng).unwrap();
+        let pks = [pk, PublicKey::from(&sk2)];
+        assert_eq!(AccountableGroup::try_new(&[]), Err(Error::Empty));
+        assert_eq!(
+            AccountableGroup::try_new(&[pk, PublicKey::default()]),
+            Err(Error::IdentityPoint)
+        );
+        let group = AccountableGroup::try_new(&pks).unwrap();
+        assert_eq!(group.try_combine(&[2], &[sig]), Err(Error::InvalidIndex(2)));
+        assert_eq!(
+            group.try_combine(&[0, 1], &[sig]),
+            Err(Error::InvalidLength {
+                expected: 2,
+                actual: 1
+            })
+        );
+
+        assert_eq!(
+            ParticipationAggregate::try_new(sig, 2, 2),
+            Err(Error::InvalidIndex(2))
+        );
+        let agg = ParticipationAggregate::try_new(sig, 0, 2).unwrap();
+        assert_eq!(agg.try_add(sig, 0), Err(Error::DuplicateIndex(0)));
+        assert!(agg.try_add(Signature::new(&sk2, msg).unwrap(), 1).is_ok());
+    }
+}
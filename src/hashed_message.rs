@@ -0,0 +1,134 @@
+use crate::{MinPk, MinSig, Scheme, SignatureOf};
+
+/// A message hashed to the signature group for signing and verifying with [`SignatureOf`].
+///
+/// Hashing to the curve is computed once and can be reused e.g. when
+/// checking a signature against several candidate keys. The domain
+/// separation tag that produced the point is kept with it and must
+/// match the tag used by [`SignatureOf`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HashedMessageOf<S: Scheme> {
+    pub(crate) point: S::SignatureGroup,
+    pub(crate) dst: &'static [u8],
+}
+
+/// A message hashed to G1 for signing and verifying with [`crate::Signature`]
+pub type HashedMessage = HashedMessageOf<MinSig>;
+
+/// A message hashed to G2 for signing and verifying with [`crate::SignatureVt`]
+pub type HashedMessageVt = HashedMessageOf<MinPk>;
+
+impl<S: Scheme> HashedMessageOf<S> {
+    /// Hash `msg` to the signature group with the signature domain separation tag
+    pub fn new<B: AsRef<[u8]>>(msg: B) -> Self {
+        Self {
+            point: SignatureOf::<S>::hash_msg(msg.as_ref()),
+            dst: S::SIGNATURE_DST,
+        }
+    }
+
+    /// The hashed point
+    pub fn point(&self) -> S::SignatureGroup {
+        self.point
+    }
+
+    /// The domain separation tag used to compute the point
+    pub fn dst(&self) -> &'static [u8] {
+        self.dst
+    }
+
+    /// Was this computed with the signature domain separation tag
+    pub(crate) fn is_signature_dst(&self) -> bool {
+        self.dst == S::SIGNATURE_DST
+    }
+}
+
+#[test]
+fn hashed_message_works() {
+    use crate::*;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([41u8; 16]);
+    let sk1 = SecretKey::random(&mut rng).unwrap();
+    let sk2 = SecretKey::random(&mut rng).unwrap();
+    let pk1 = PublicKey::from(&sk1);
+    let pk2 = PublicKey::from(&sk2);
+    let msg = HashedMessage::new(b"hashed_message");
+    let other = HashedMessage::new(b"another message");
+
+    let sig1 = Signature::new_prehashed(&sk1, &msg).unwrap();
+    assert_eq!(sig1, Signature::new(&sk1, b"hashed_message").unwrap());
+    assert_eq!(sig1.verify_prehashed(pk1, &msg).unwrap_u8(), 1u8);
+    assert_eq!(sig1.verify_prehashed(pk2, &msg).unwrap_u8(), 0u8);
+    assert_eq!(sig1.verify_prehashed(pk1, &other).unwrap_u8(), 0u8);
+
+    let sig2 = Signature::new_prehashed(&sk2, &msg).unwrap();
+    let msig = MultiSignature::from(&[sig1, sig2].map(|s| Validated::new(s).unwrap())[..]);
+    let mpk = MultiPublicKey::from(&[pk1, pk2].map(|k| Validated::new(k).unwrap())[..]);
+    assert_eq!(msig.verify_prehashed(mpk, &msg).unwrap_u8(), 1u8);
+
+    let sig3 = Signature::new_prehashed(&sk2, &other).unwrap();
+    let asig = AggregateSignature::from(&[sig1, sig3].map(|s| Validated::new(s).unwrap())[..]);
+    assert_eq!(
+        asig.verify_prehashed(&[(pk1, msg), (pk2, other)])
+            .unwrap_u8(),
+        1u8
+    );
+    assert_eq!(
+        asig.verify_prehashed(&[(pk1, other), (pk2, msg)])
+            .unwrap_u8(),
+        0u8
+    );
+
+    let wrong_dst = HashedMessage {
+        point: msg.point,
+        dst: MinSig::POP_DST,
+    };
+    assert!(Signature::new_prehashed(&sk1, &wrong_dst).is_none());
+    assert_eq!(sig1.verify_prehashed(pk1, &wrong_dst).unwrap_u8(), 0u8);
+}
+
+#[test]
+fn hashed_message_vt_works() {
+    use crate::*;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([41u8; 16]);
+    let sk1 = SecretKey::random(&mut rng).unwrap();
+    let sk2 = SecretKey::random(&mut rng).unwrap();
+    let pk1 = PublicKeyVt::from(&sk1);
+    let pk2 = PublicKeyVt::from(&sk2);
+    let msg = HashedMessageVt::new(b"hashed_message");
+    let other = HashedMessageVt::new(b"another message");
+
+    let sig1 = SignatureVt::new_prehashed(&sk1, &msg).unwrap();
+    assert_eq!(sig1, SignatureVt::new(&sk1, b"hashed_message").unwrap());
+    assert_eq!(sig1.verify_prehashed(pk1, &msg).unwrap_u8(), 1u8);
+    assert_eq!(sig1.verify_prehashed(pk2, &msg).unwrap_u8(), 0u8);
+    assert_eq!(sig1.verify_prehashed(pk1, &other).unwrap_u8(), 0u8);
+
+    let sig2 = SignatureVt::new_prehashed(&sk2, &msg).unwrap();
+    let msig = MultiSignatureVt::from(&[sig1, sig2].map(|s| Validated::new(s).unwrap())[..]);
+    let mpk = MultiPublicKeyVt::from(&[pk1, pk2].map(|k| Validated::new(k).unwrap())[..]);
+    assert_eq!(msig.verify_prehashed(mpk, &msg).unwrap_u8(), 1u8);
+
+    let sig3 = SignatureVt::new_prehashed(&sk2, &other).unwrap();
+    let asig = AggregateSignatureVt::from(&[sig1, sig3].map(|s| Validated::new(s).unwrap())[..]);
+    assert_eq!(
+        asig.verify_prehashed(&[(pk1, msg), (pk2, other)])
+            .unwrap_u8(),
+        1u8
+    );
+    assert_eq!(
+        asig.verify_prehashed(&[(pk1, other), (pk2, msg)])
+            .unwrap_u8(),
+        0u8
+    );
+
+    let wrong_dst = HashedMessageVt {
+        point: msg.point,
+        dst: MinPk::POP_DST,
+    };
+    assert!(SignatureVt::new_prehashed(&sk1, &wrong_dst).is_none());
+    assert_eq!(sig1.verify_prehashed(pk1, &wrong_dst).unwrap_u8(), 0u8);
+}
@@ -0,0 +1,231 @@
+use crate::{Error, PublicKey, PublicKeyVt, SecretKey, Signature, SignatureVt};
+use alloc::{format, string::String, vec::Vec};
+use base64ct::{Base64UrlUnpadded, Encoding};
+use serde::{Deserialize, Serialize};
+use zeroize::{Zeroize, Zeroizing};
+
+/// JWK `kty` for keys with the compressed point in `x`
+pub const JWK_KTY_OKP: &str = "OKP";
+/// JWK `kty` for keys with the affine coordinates in `x` and `y`
+pub const JWK_KTY_EC2: &str = "EC2";
+/// JWK `crv` for keys in G1
+pub const JWK_CRV_BLS12381G1: &str = "BLS12381G1";
+/// JWK `crv` for keys in G2
+pub const JWK_CRV_BLS12381G2: &str = "BLS12381G2";
+
+/// A JSON Web Key for BLS12-381 keys as described in
+/// <https://datatracker.ietf.org/doc/draft-ietf-cose-bls-key-representations/>
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize, Zeroize)]
+#[zeroize(drop)]
+pub struct Jwk {
+    /// The key type, `OKP` or `EC2`
+    pub kty: String,
+    /// The curve, `BLS12381G1` or `BLS12381G2`
+    pub crv: String,
+    /// The base64url compressed point for `OKP`, or the x-coordinate for `EC2`
+    pub x: String,
+    /// The base64url y-coordinate for `EC2`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    /// The base64url big-endian secret key
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
+}
+
+fn decode_field(s: &str) -> Result<Vec<u8>, Error> {
+    Base64UrlUnpadded::decode_vec(s).map_err(|_| Error::InvalidEncoding)
+}
+
+macro_rules! jwk_impl {
+    ($public_key:ident, $crv:expr, $coord:expr) => {
+        impl $public_key {
+            /// Get the JWK with `kty: OKP` and the compressed point
+            pub fn to_jwk(&self) -> Jwk {
+                Jwk {
+                    kty: String::from(JWK_KTY_OKP),
+                    crv: String::from($crv),
+                    x: Base64UrlUnpadded::encode_string(&self.to_bytes()),
+                    y: None,
+                    d: None,
+                }
+            }
+
+            /// Get the JWK with `kty: EC2` and the affine coordinates
+            pub fn to_jwk_ec2(&self) -> Jwk {
+                let bytes = self.to_uncompressed_bytes();
+                Jwk {
+                    kty: String::from(JWK_KTY_EC2),
+                    crv: String::from($crv),
+                    x: Base64UrlUnpadded::encode_string(&bytes[..$coord]),
+                    y: Some(Base64UrlUnpadded::encode_string(&bytes[$coord..])),
+                    d: None,
+                }
+            }
+
+            /// Convert a JWK in either form, ignoring any secret key
+            pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+                if jwk.crv != $crv {
+                    return Err(Error::InvalidEncoding);
+                }
+                let mut x = decode_field(&jwk.x)?;
+                match (jwk.kty.as_str(), &jwk.y) {
+                    (JWK_KTY_OKP, None) => Self::try_from_bytes(&x),
+                    (JWK_KTY_EC2, Some(y)) => {
+                        if x.len() != $coord {
+                            return Err(Error::InvalidLength {
+                                expected: $coord,
+                                actual: x.len(),
+                            });
+                        }
+                        x.extend_from_slice(&decode_field(y)?);
+                        Self::try_from_uncompressed_bytes(&x)
+                    }
+                    _ => Err(Error::InvalidEncoding),
+                }
+            }
+        }
+    };
+}
+
+jwk_impl!(PublicKey, JWK_CRV_BLS12381G2, 96);
+jwk_impl!(PublicKeyVt, JWK_CRV_BLS12381G1, 48);
+
+impl SecretKey {
+    /// Get the JWK with the public key in G2 for signing with [`Signature`]
+    pub fn to_jwk(&self) -> Jwk {
+        let mut jwk = PublicKey::from(self).to_jwk();
+        jwk.d = Some(Base64UrlUnpadded::encode_string(&self.to_bytes()));
+        jwk
+    }
+
+    /// Get the JWK with the public key in G1 for signing with [`SignatureVt`]
+    pub fn to_jwk_vt(&self) -> Jwk {
+        let mut jwk = PublicKeyVt::from(self).to_jwk();
+        jwk.d = Some(Base64UrlUnpadded::encode_string(&self.to_bytes()));
+        jwk
+    }
+
+    /// Convert a JWK with a secret key and check it matches the public key
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        let d = jwk.d.as_deref().ok_or(Error::InvalidEncoding)?;
+        let d = Zeroizing::new(decode_field(d)?);
+        let sk = Self::try_from_bytes(&d)?;
+        let matches = match jwk.crv.as_str() {
+            JWK_CRV_BLS12381G2 => PublicKey::from_jwk(jwk)? == PublicKey::from(&sk),
+            JWK_CRV_BLS12381G1 => PublicKeyVt::from_jwk(jwk)? == PublicKeyVt::from(&sk),
+            _ => false,
+        };
+        if !matches {
+            return Err(Error::InvalidEncoding);
+        }
+        Ok(sk)
+    }
+}
+
+/// The JWS protected header fields that are checked
+#[derive(Deserialize)]
+struct JwsHeader {
+    alg: String,
+    #[serde(default)]
+    crit: Option<Vec<String>>,
+}
+
+fn jws_signing_input(alg: &str, payload: &[u8]) -> String {
+    let header = format!("{{\"alg\":\"{}\"}}", alg);
+    format!(
+        "{}.{}",
+        Base64UrlUnpadded::encode_string(header.as_bytes()),
+        Base64UrlUnpadded::encode_string(payload)
+    )
+}
+
+/// Split a compact JWS into the signing input, payload and signature
+fn jws_parts<'a>(jws: &'a str, alg: &str) -> Result<(&'a str, Vec<u8>, Vec<u8>), Error> {
+    let (input, sig) = jws.rsplit_once('.').ok_or(Error::InvalidEncoding)?;
+    let (header, payload) = input.split_once('.').ok_or(Error::InvalidEncoding)?;
+    let header: JwsHeader =
+        serde_json::from_slice(&decode_field(header)?).map_err(|_| Error::InvalidEncoding)?;
+    // No extensions are understood so any critical ones must be rejected
+    if header.alg != alg || header.crit.is_some() {
+        return Err(Error::InvalidEncoding);
+    }
+    Ok((input, decode_field(payload)?, decode_field(sig)?))
+}
+
+macro_rules! jws_impl {
+    ($signature:ident, $public_key:ident, $alg:expr) => {
+        impl $signature {
+            /// The JWS `alg` for this signature.
+            /// There is no registered value yet so this is a private name.
+            pub const JWS_ALG: &'static str = $alg;
+
+            /// Sign `payload` and return the JWS compact serialization
+            pub fn sign_jws(sk: &SecretKey, payload: &[u8]) -> Option<String> {
+                let input = jws_signing_input(Self::JWS_ALG, payload);
+                let sig = Self::new(sk, input.as_bytes())?;
+                Some(format!(
+                    "{}.{}",
+                    input,
+                    Base64UrlUnpadded::encode_string(&sig.to_bytes())
+                ))
+            }
+
+            /// Verify a JWS compact serialization and return the payload
+            pub fn verify_jws(pk: $public_key, jws: &str) -> Result<Vec<u8>, Error> {
+                let (input, payload, sig) = jws_parts(jws, Self::JWS_ALG)?;
+                Self::try_from_bytes(&sig)?.try_verify(pk, input)?;
+                Ok(payload)
+            }
+        }
+    };
+}
+
+jws_impl!(Signature, PublicKey, "BLS12381G1_POP");
+jws_impl!(SignatureVt, PublicKeyVt, "BLS12381G2_POP");
+
+#[test]
+fn jose_works() {
+    use crate::MockRng;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([73u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let pk_vt = PublicKeyVt::from(&sk);
+
+    let jwk = pk.to_jwk();
+    assert_eq!(jwk.kty, "OKP");
+    assert_eq!(jwk.crv, "BLS12381G2");
+    assert_eq!(PublicKey::from_jwk(&jwk).unwrap(), pk);
+    assert!(PublicKeyVt::from_jwk(&jwk).is_err());
+    let jwk = pk.to_jwk_ec2();
+    assert_eq!(jwk.kty, "EC2");
+    assert_eq!(PublicKey::from_jwk(&jwk).unwrap(), pk);
+    let jwk = pk_vt.to_jwk_ec2();
+    assert_eq!(PublicKeyVt::from_jwk(&jwk).unwrap(), pk_vt);
+
+    let jwk = sk.to_jwk();
+    let json = serde_json::to_string(&jwk).unwrap();
+    assert!(!json.contains("\"y\""));
+    let jwk = serde_json::from_str::<Jwk>(&json).unwrap();
+    assert_eq!(SecretKey::from_jwk(&jwk).unwrap(), sk);
+    assert_eq!(PublicKey::from_jwk(&jwk).unwrap(), pk);
+    assert_eq!(SecretKey::from_jwk(&sk.to_jwk_vt()).unwrap(), sk);
+    let other = SecretKey::random(&mut rng).unwrap();
+    let mut jwk = sk.to_jwk();
+    jwk.x = other.to_jwk().x.clone();
+    assert!(SecretKey::from_jwk(&jwk).is_err());
+    assert!(SecretKey::from_jwk(&pk.to_jwk()).is_err());
+
+    let payload = b"{\"iss\":\"device\"}";
+    let jws = Signature::sign_jws(&sk, payload).unwrap();
+    assert_eq!(jws.split('.').count(), 3);
+    assert_eq!(Signature::verify_jws(pk, &jws).unwrap(), payload);
+    assert!(Signature::verify_jws(PublicKey::from(&other), &jws).is_err());
+    let (input, _) = jws.rsplit_once('.').unwrap();
+    let forged = format!("{}.{}", input, Base64UrlUnpadded::encode_string(b"x"));
+    assert!(Signature::verify_jws(pk, &forged).is_err());
+    let jws = SignatureVt::sign_jws(&sk, payload).unwrap();
+    assert_eq!(SignatureVt::verify_jws(pk_vt, &jws).unwrap(), payload);
+    assert!(Signature::verify_jws(pk, &jws).is_err());
+}
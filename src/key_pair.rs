@@ -0,0 +1,134 @@
+use crate::{MinPk, MinSig, PublicKeyOf, Scheme, SecretKey, SignatureOf};
+use rand_core::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// A secret key with its public key for signing with [`SignatureOf`].
+///
+/// Implements the RustCrypto `Signer`, `Verifier` and `Keypair` traits
+/// so it can be used with code written against the `signature` crate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyPairOf<S: Scheme> {
+    secret_key: SecretKey,
+    public_key: PublicKeyOf<S>,
+}
+
+/// A key pair for signing with [`crate::Signature`]
+pub type KeyPair = KeyPairOf<MinSig>;
+
+/// A key pair for signing with [`crate::SignatureVt`]
+pub type KeyPairVt = KeyPairOf<MinPk>;
+
+impl<S: Scheme> Zeroize for KeyPairOf<S> {
+    fn zeroize(&mut self) {
+        self.secret_key.zeroize();
+    }
+}
+
+// The secret key zeroizes itself when dropped
+impl<S: Scheme> ZeroizeOnDrop for KeyPairOf<S> {}
+
+impl<S: Scheme> From<SecretKey> for KeyPairOf<S> {
+    fn from(secret_key: SecretKey) -> Self {
+        let public_key = PublicKeyOf::from(&secret_key);
+        Self {
+            secret_key,
+            public_key,
+        }
+    }
+}
+
+impl<S: Scheme> From<&SecretKey> for KeyPairOf<S> {
+    fn from(secret_key: &SecretKey) -> Self {
+        Self::from(secret_key.clone())
+    }
+}
+
+impl<S: Scheme> AsRef<PublicKeyOf<S>> for KeyPairOf<S> {
+    fn as_ref(&self) -> &PublicKeyOf<S> {
+        &self.public_key
+    }
+}
+
+impl<S: Scheme> KeyPairOf<S> {
+    /// Generate a new key pair from a CS-PRNG
+    pub fn random(rng: impl RngCore + CryptoRng) -> Option<Self> {
+        SecretKey::random(rng).map(Self::from)
+    }
+
+    /// The secret key
+    pub fn secret_key(&self) -> &SecretKey {
+        &self.secret_key
+    }
+
+    /// The public key
+    pub fn public_key(&self) -> PublicKeyOf<S> {
+        self.public_key
+    }
+}
+
+impl<S: Scheme> signature::KeypairRef for KeyPairOf<S> {
+    type VerifyingKey = PublicKeyOf<S>;
+}
+
+impl<S: Scheme> signature::Signer<SignatureOf<S>> for KeyPairOf<S> {
+    fn try_sign(&self, msg: &[u8]) -> Result<SignatureOf<S>, signature::Error> {
+        signature::Signer::<SignatureOf<S>>::try_sign(&self.secret_key, msg)
+    }
+}
+
+impl<S: Scheme> signature::Verifier<SignatureOf<S>> for KeyPairOf<S> {
+    fn verify(&self, msg: &[u8], sig: &SignatureOf<S>) -> Result<(), signature::Error> {
+        signature::Verifier::<SignatureOf<S>>::verify(&self.public_key, msg, sig)
+    }
+}
+
+#[test]
+fn key_pair_works() {
+    use crate::{MockRng, PublicKey, Signature};
+    use rand_core::SeedableRng;
+    use signature::{Keypair, SignatureEncoding, Signer, Verifier};
+
+    let mut rng = MockRng::from_seed([47u8; 16]);
+    let kp = KeyPair::random(&mut rng).unwrap();
+    assert_eq!(kp.verifying_key(), PublicKey::from(kp.secret_key()));
+
+    let msg = b"key_pair";
+    let sig: Signature = kp.sign(msg);
+    assert_eq!(sig, Signature::new(kp.secret_key(), msg).unwrap());
+    assert!(kp.verify(msg, &sig).is_ok());
+    assert!(kp.public_key().verify(b"another message", &sig).is_err());
+    #[cfg(feature = "std")]
+    {
+        use std::error::Error as _;
+        let err = kp.verify(b"another message", &sig).unwrap_err();
+        let source = err.source().unwrap().downcast_ref::<crate::Error>();
+        assert!(matches!(source, Some(crate::Error::VerificationFailed)));
+    }
+
+    let bytes = SignatureEncoding::to_bytes(&sig);
+    assert_eq!(Signature::try_from(&bytes[..]).unwrap(), sig);
+    assert!(Signature::try_from(&bytes[1..]).is_err());
+    assert!(Signature::try_from(&[0xffu8; Signature::BYTES][..]).is_err());
+}
+
+#[test]
+fn key_pair_vt_works() {
+    use crate::{MockRng, PublicKeyVt, SignatureVt};
+    use rand_core::SeedableRng;
+    use signature::{Keypair, SignatureEncoding, Signer, Verifier};
+
+    let mut rng = MockRng::from_seed([48u8; 16]);
+    let kp = KeyPairVt::random(&mut rng).unwrap();
+    assert_eq!(kp.verifying_key(), PublicKeyVt::from(kp.secret_key()));
+
+    let msg = b"key_pair_vt";
+    let sig: SignatureVt = kp.sign(msg);
+    assert_eq!(sig, SignatureVt::new(kp.secret_key(), msg).unwrap());
+    assert!(kp.verify(msg, &sig).is_ok());
+    assert!(kp.public_key().verify(b"another message", &sig).is_err());
+
+    let bytes = SignatureEncoding::to_bytes(&sig);
+    assert_eq!(SignatureVt::try_from(&bytes[..]).unwrap(), sig);
+    assert!(SignatureVt::try_from(&bytes[1..]).is_err());
+    assert!(SignatureVt::try_from(&[0xffu8; SignatureVt::BYTES][..]).is_err());
+}
@@ -0,0 +1,245 @@
+use crate::{MinPk, MinSig, MultiPublicKeyOf, ProofOfPossessionOf, PublicKeyOf, Scheme};
+use alloc::{collections::BTreeMap, vec::Vec};
+use group::Group;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// A handle to a public key whose proof of possession has been verified
+/// by a [`KeyRegistryOf`]. Handles can only be created by a registry so
+/// only verified keys can be aggregated with [`MultiPublicKeyOf::from_verified`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "PublicKeyOf<S>: Serialize")]
+pub struct VerifiedKeyOf<S: Scheme> {
+    index: usize,
+    key: PublicKeyOf<S>,
+}
+
+/// A handle to a verified public key in G2
+pub type VerifiedKey = VerifiedKeyOf<MinSig>;
+
+/// A handle to a verified public key in G1
+pub type VerifiedKeyVt = VerifiedKeyOf<MinPk>;
+
+impl<S: Scheme> VerifiedKeyOf<S> {
+    /// The index assigned to this key by the registry
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// The verified public key
+    pub fn public_key(&self) -> PublicKeyOf<S> {
+        self.key
+    }
+}
+
+impl<S: Scheme> MultiPublicKeyOf<S> {
+    /// Aggregate keys that have had their proofs of possession verified
+    pub fn from_verified(keys: &[VerifiedKeyOf<S>]) -> Self {
+        let mut g = S::PublicKeyGroup::identity();
+        for k in keys {
+            g += k.key.0;
+        }
+        Self(g)
+    }
+}
+
+/// A set of public keys that have proven possession of their secret keys.
+///
+/// Keys are deduplicated and assigned a stable index in the order they are
+/// registered. The registry serializes the keys with their proofs and
+/// checks every proof again when deserialized.
+#[derive(Clone, Debug, Default)]
+pub struct KeyRegistryOf<S: Scheme> {
+    entries: Vec<(PublicKeyOf<S>, ProofOfPossessionOf<S>)>,
+    lookup: BTreeMap<Vec<u8>, usize>,
+}
+
+/// A registry of public keys in G2
+pub type KeyRegistry = KeyRegistryOf<MinSig>;
+
+/// A registry of public keys in G1
+pub type KeyRegistryVt = KeyRegistryOf<MinPk>;
+
+impl<S: Scheme> KeyRegistryOf<S> {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of registered keys
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Is the registry empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Register `pk` if `pop` is valid.
+    /// If `pk` is already registered the existing handle is returned.
+    pub fn register(
+        &mut self,
+        pk: PublicKeyOf<S>,
+        pop: ProofOfPossessionOf<S>,
+    ) -> Option<VerifiedKeyOf<S>> {
+        if let Some(k) = self.find(&pk) {
+            return Some(k);
+        }
+        if pop.verify(pk).unwrap_u8() == 0 {
+            return None;
+        }
+        Some(self.insert(pk, pop))
+    }
+
+    /// Register many keys at once using batch verification of the proofs.
+    /// The output has one entry per input which is `None` if the proof is invalid.
+    pub fn register_batch(
+        &mut self,
+        keys: &[(PublicKeyOf<S>, ProofOfPossessionOf<S>)],
+        rng: impl rand_core::RngCore + rand_core::CryptoRng,
+    ) -> Vec<Option<VerifiedKeyOf<S>>> {
+        let proofs = keys.iter().map(|(pk, pop)| (*pop, *pk)).collect::<Vec<_>>();
+        let invalid = ProofOfPossessionOf::batch_find_invalid(&proofs, rng);
+        keys.iter()
+            .enumerate()
+            .map(|(i, (pk, pop))| {
+                if invalid.binary_search(&i).is_ok() {
+                    return None;
+                }
+                match self.find(pk) {
+                    Some(k) => Some(k),
+                    None => Some(self.insert(*pk, *pop)),
+                }
+            })
+            .collect()
+    }
+
+    /// Get the handle for the key at `index`
+    pub fn get(&self, index: usize) -> Option<VerifiedKeyOf<S>> {
+        self.entries
+            .get(index)
+            .map(|(key, _)| VerifiedKeyOf { index, key: *key })
+    }
+
+    /// Get the handle for `pk` if it's registered
+    pub fn find(&self, pk: &PublicKeyOf<S>) -> Option<VerifiedKeyOf<S>> {
+        self.lookup
+            .get(pk.to_bytes().as_ref())
+            .map(|index| VerifiedKeyOf {
+                index: *index,
+                key: *pk,
+            })
+    }
+
+    /// Iterate over all registered keys in index order
+    pub fn iter(&self) -> impl Iterator<Item = VerifiedKeyOf<S>> + '_ {
+        self.entries
+            .iter()
+            .enumerate()
+            .map(|(index, (key, _))| VerifiedKeyOf { index, key: *key })
+    }
+
+    fn insert(&mut self, pk: PublicKeyOf<S>, pop: ProofOfPossessionOf<S>) -> VerifiedKeyOf<S> {
+        let index = self.entries.len();
+        self.entries.push((pk, pop));
+        self.lookup.insert(pk.to_bytes().as_ref().to_vec(), index);
+        VerifiedKeyOf { index, key: pk }
+    }
+}
+
+impl<S: Scheme> Serialize for KeyRegistryOf<S> {
+    fn serialize<Ser>(&self, s: Ser) -> Result<Ser::Ok, Ser::Error>
+    where
+        Ser: Serializer,
+    {
+        self.entries.serialize(s)
+    }
+}
+
+impl<'de, S: Scheme> Deserialize<'de> for KeyRegistryOf<S> {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let entries = Vec::<(PublicKeyOf<S>, ProofOfPossessionOf<S>)>::deserialize(d)?;
+        let mut registry = Self::new();
+        for (pk, pop) in entries {
+            if registry.find(&pk).is_some() {
+                return Err(serde::de::Error::custom("duplicate public key"));
+            }
+            if registry.register(pk, pop).is_none() {
+                return Err(serde::de::Error::custom("invalid proof of possession"));
+            }
+        }
+        Ok(registry)
+    }
+}
+
+#[test]
+fn key_registry_works() {
+    use crate::{
+        MockRng, MultiPublicKey, MultiPublicKeyVt, MultiSignature, MultiSignatureVt,
+        ProofOfPossession, ProofOfPossessionVt, PublicKey, PublicKeyVt, SecretKey, Signature,
+        SignatureVt, Validated,
+    };
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([31u8; 16]);
+    let sks = (0..4)
+        .map(|_| SecretKey::random(&mut rng).unwrap())
+        .collect::<Vec<_>>();
+    let pks = sks.iter().map(PublicKey::from).collect::<Vec<_>>();
+    let pops = sks
+        .iter()
+        .map(|sk| ProofOfPossession::new(sk).unwrap())
+        .collect::<Vec<_>>();
+
+    let mut registry = KeyRegistry::new();
+    let k0 = registry.register(pks[0], pops[0]).unwrap();
+    assert_eq!(k0.index(), 0);
+    assert_eq!(registry.register(pks[0], pops[0]), Some(k0));
+    assert!(registry.register(pks[1], pops[2]).is_none());
+
+    let batch = registry.register_batch(
+        &[(pks[1], pops[1]), (pks[2], pops[3]), (pks[3], pops[3])],
+        &mut rng,
+    );
+    assert_eq!(batch[0].unwrap().index(), 1);
+    assert!(batch[1].is_none());
+    assert_eq!(batch[2].unwrap().index(), 2);
+    assert_eq!(registry.len(), 3);
+
+    let msg = b"key_registry";
+    let handles = registry.iter().collect::<Vec<_>>();
+    let sigs = [0usize, 1, 3]
+        .iter()
+        .map(|i| Validated::new(Signature::new(&sks[*i], msg).unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    let mpk = MultiPublicKey::from_verified(&handles);
+    let msig = MultiSignature::from(sigs.as_slice());
+    assert_eq!(msig.verify(mpk, msg).unwrap_u8(), 1u8);
+
+    let bytes = serde_bare::to_vec(&registry).unwrap();
+    let de_registry = serde_bare::from_slice::<KeyRegistry>(&bytes).unwrap();
+    assert_eq!(de_registry.iter().collect::<Vec<_>>(), handles);
+    assert_eq!(de_registry.find(&pks[3]).unwrap().index(), 2);
+
+    let mut registry = KeyRegistryVt::new();
+    for sk in &sks[..2] {
+        let pk = PublicKeyVt::from(sk);
+        assert!(registry
+            .register(pk, ProofOfPossessionVt::new(sk).unwrap())
+            .is_some());
+    }
+    let handles = registry.iter().collect::<Vec<_>>();
+    let sigs = sks[..2]
+        .iter()
+        .map(|sk| Validated::new(SignatureVt::new(sk, msg).unwrap()).unwrap())
+        .collect::<Vec<_>>();
+    let mpk = MultiPublicKeyVt::from_verified(&handles);
+    let msig = MultiSignatureVt::from(sigs.as_slice());
+    assert_eq!(msig.verify(mpk, msg).unwrap_u8(), 1u8);
+    let bytes = serde_bare::to_vec(&registry).unwrap();
+    let de_registry = serde_bare::from_slice::<KeyRegistryVt>(&bytes).unwrap();
+    assert_eq!(de_registry.iter().collect::<Vec<_>>(), handles);
+}
@@ -8,10 +8,28 @@
 //! Normal puts signatures in G1 and pubic keys in G2.
 //! Variant is the reverse.
 //!
+//! Both are the same generic types, e.g. `SignatureOf<S>`, instantiated with
+//! the [`MinSig`] or [`MinPk`] [`Scheme`] so `Signature` is `SignatureOf<MinSig>`
+//! and `SignatureVt` is `SignatureOf<MinPk>`.
+//!
+//! Only BLS12-381 is supported. [`Point`] fixes the scalar field to BLS12-381's
+//! and hashing to the curve uses the BLS12-381 suites from `bls12_381_plus`,
+//! so other pairing-friendly curves such as BLS12-377 or BN254 would need
+//! the scalar and the hash-to-curve suite to become part of [`Scheme`].
+//!
 //! This crate has been designed to be compliant with no-std by avoiding allocations
 //!
 //! but provides some optimizations when an allocator exists for verifying
 //! aggregated signatures.
+//!
+//! The `parallel` feature uses rayon to spread hashing, miller loops
+//! and point sums across threads when verifying aggregates and batches.
+//!
+//! The `jose` feature adds JWK and JWS support and the `cose` feature
+//! adds COSE_Key and COSE_Sign1 support.
+//! The `pkcs8` feature implements the `pkcs8` and `spki` encoding traits
+//! with OIDs under this crate's private arc, and adds `*_with_oid` methods
+//! for algorithm OIDs supplied by the caller.
 #![deny(unsafe_code)]
 #![warn(
     missing_docs,
@@ -31,45 +49,97 @@ extern crate alloc;
 #[macro_use]
 mod macros;
 
+#[cfg(feature = "alloc")]
+mod accountable_subgroup;
+#[cfg(feature = "alloc")]
+mod aggregate_proof_of_knowledge;
 mod aggregate_signature;
-mod aggregate_signature_vt;
+#[cfg(feature = "alloc")]
+mod authenticator;
+mod clock;
+#[cfg(feature = "cose")]
+mod cose;
+#[cfg(feature = "alloc")]
+mod did_key;
+mod error;
+mod hashed_message;
+#[cfg(feature = "jose")]
+mod jose;
+mod key_pair;
+#[cfg(feature = "alloc")]
+mod key_registry;
+mod message_hasher;
 mod multi_public_key;
-mod multi_public_key_vt;
 mod multi_signature;
-mod multi_signature_vt;
+#[cfg(feature = "alloc")]
+mod multiformats;
+#[cfg(feature = "parallel")]
+mod parallel;
 mod partial_signature;
-mod partial_signature_vt;
+#[cfg(feature = "alloc")]
+mod participation_aggregate;
+#[cfg(feature = "pkcs8")]
+mod pkix;
+mod prepared_public_key;
 mod proof_of_knowledge;
-mod proof_of_knowledge_vt;
 mod proof_of_possession;
-mod proof_of_possession_vt;
 mod public_key;
-mod public_key_vt;
+mod scheme;
 mod secret_key;
 mod secret_key_share;
+mod serdes;
 mod signature;
-mod signature_vt;
+mod validated;
+#[cfg(feature = "alloc")]
+mod weighted_threshold;
 
+#[cfg(feature = "alloc")]
+pub use accountable_subgroup::*;
+#[cfg(feature = "alloc")]
+pub use aggregate_proof_of_knowledge::*;
 pub use aggregate_signature::*;
-pub use aggregate_signature_vt::*;
+#[cfg(feature = "alloc")]
+pub use authenticator::*;
+pub use clock::*;
+#[cfg(feature = "cose")]
+pub use cose::*;
+#[cfg(feature = "alloc")]
+pub use did_key::*;
+pub use error::*;
+pub use hashed_message::*;
+#[cfg(feature = "jose")]
+pub use jose::*;
+pub use key_pair::*;
+#[cfg(feature = "alloc")]
+pub use key_registry::*;
+pub use message_hasher::*;
 pub use multi_public_key::*;
-pub use multi_public_key_vt::*;
 pub use multi_signature::*;
-pub use multi_signature_vt::*;
+#[cfg(feature = "alloc")]
+pub use multiformats::*;
 pub use partial_signature::*;
-pub use partial_signature_vt::*;
+#[cfg(feature = "alloc")]
+pub use participation_aggregate::*;
+#[cfg(feature = "pkcs8")]
+pub use pkix::*;
+pub use prepared_public_key::*;
 pub use proof_of_knowledge::*;
-pub use proof_of_knowledge_vt::*;
 pub use proof_of_possession::*;
-pub use proof_of_possession_vt::*;
 pub use public_key::*;
-pub use public_key_vt::*;
+pub use scheme::*;
 pub use secret_key::*;
 pub use secret_key_share::*;
-pub use signature::*;
-pub use signature_vt::*;
+pub use serdes::{base64, uncompressed, ByteEncoding, UncompressedEncoding};
+pub use self::signature::*;
+pub use validated::*;
+#[cfg(feature = "alloc")]
+pub use weighted_threshold::*;
 
 pub use bls12_381_plus;
+#[cfg(feature = "pkcs8")]
+pub use pkcs8;
+#[cfg(feature = "alloc")]
+pub use multibase;
 pub use vsss_rs;
 
 #[cfg(test)]
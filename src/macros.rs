@@ -1,49 +1,195 @@
 macro_rules! validity_checks {
     () => {
-        /// Check if this is valid
+        /// Check if this is valid, i.e. not the identity, on the curve and
+        /// in the prime order subgroup. For public keys this is `KeyValidate`
+        /// and for signatures the subgroup check from
+        /// <https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05>
         pub fn is_valid(&self) -> Choice {
-            !self.0.is_identity() | !self.0.is_on_curve()
+            !self.0.is_identity()
+                & crate::Point::is_on_curve(&self.0)
+                & crate::Point::is_torsion_free(&self.0)
         }
 
         /// Check if this is invalid
         pub fn is_invalid(&self) -> Choice {
-            self.0.is_identity() | !self.0.is_on_curve()
+            !self.is_valid()
+        }
+
+        /// Check this is a point in the prime order subgroup
+        /// and return the reason if it isn't
+        pub fn validate(&self) -> Result<(), crate::Error> {
+            crate::Error::check_point(
+                self.0.is_identity(),
+                crate::Point::is_on_curve(&self.0),
+                crate::Point::is_torsion_free(&self.0),
+            )
+        }
+
+        /// Check a batch of points, e.g. those loaded with the unchecked
+        /// byte conversions, and return the index and reason of the first invalid one
+        pub fn validate_batch(items: &[Self]) -> Result<(), (usize, crate::Error)> {
+            #[cfg(feature = "parallel")]
+            {
+                use rayon::prelude::*;
+
+                match items
+                    .par_iter()
+                    .map(Self::validate)
+                    .enumerate()
+                    .find_first(|(_, r)| r.is_err())
+                {
+                    Some((i, Err(e))) => Err((i, e)),
+                    _ => Ok(()),
+                }
+            }
+            #[cfg(not(feature = "parallel"))]
+            {
+                for (i, item) in items.iter().enumerate() {
+                    item.validate().map_err(|e| (i, e))?;
+                }
+                Ok(())
+            }
         }
     };
 }
 
 macro_rules! bytes_impl {
-    ($affine:ident, $projective:ident) => {
+    ($point:ty) => {
+        /// Number of bytes needed to represent the uncompressed point
+        pub const UNCOMPRESSED_BYTES: usize = Self::BYTES * 2;
+
         /// Get the byte representation
-        pub fn to_bytes(self) -> [u8; Self::BYTES] {
-            self.0.to_affine().to_compressed()
+        pub fn to_bytes(self) -> <$point as crate::Point>::Bytes {
+            crate::Point::to_compressed(&self.0)
         }
 
         /// Convert a big-endian representation
-        pub fn from_bytes(bytes: &[u8; Self::BYTES]) -> CtOption<Self> {
-            $affine::from_compressed(bytes).map(|p| Self($projective::from(&p)))
+        pub fn from_bytes(bytes: &<$point as crate::Point>::Bytes) -> CtOption<Self> {
+            <$point as crate::Point>::from_compressed(bytes).map(Self)
+        }
+
+        /// Convert a big-endian representation and return the reason if it's invalid
+        pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+            let bytes = crate::Error::check_bytes(bytes, Self::BYTES)?;
+            let p = Option::from(<$point as crate::Point>::from_compressed_unchecked(&bytes))
+                .ok_or(crate::Error::InvalidEncoding)?;
+            let res = Self(p);
+            res.validate()?;
+            Ok(res)
+        }
+
+        /// Get the uncompressed byte representation
+        pub fn to_uncompressed_bytes(self) -> <$point as crate::Point>::UncompressedBytes {
+            crate::Point::to_uncompressed(&self.0)
+        }
+
+        /// Convert an uncompressed big-endian representation
+        pub fn from_uncompressed_bytes(
+            bytes: &<$point as crate::Point>::UncompressedBytes,
+        ) -> CtOption<Self> {
+            <$point as crate::Point>::from_uncompressed(bytes).map(Self)
+        }
+
+        /// Convert an uncompressed big-endian representation
+        /// and return the reason if it's invalid
+        pub fn try_from_uncompressed_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+            let bytes = crate::Error::check_bytes(bytes, Self::UNCOMPRESSED_BYTES)?;
+            let p = Option::from(<$point as crate::Point>::from_uncompressed_unchecked(
+                &bytes,
+            ))
+            .ok_or(crate::Error::InvalidEncoding)?;
+            let res = Self(p);
+            res.validate()?;
+            Ok(res)
+        }
+
+        /// Convert a big-endian representation without the prime order subgroup check.
+        ///
+        /// **Only use this with trusted input.** The result can be checked
+        /// later with `validate` or `validate_batch`.
+        pub fn from_bytes_unchecked(bytes: &<$point as crate::Point>::Bytes) -> CtOption<Self> {
+            <$point as crate::Point>::from_compressed_unchecked(bytes).map(Self)
+        }
+
+        /// Convert an uncompressed big-endian representation without
+        /// checking the point is on the curve or in the prime order subgroup.
+        ///
+        /// **Only use this with trusted input.** The result can be checked
+        /// later with `validate` or `validate_batch`.
+        pub fn from_uncompressed_bytes_unchecked(
+            bytes: &<$point as crate::Point>::UncompressedBytes,
+        ) -> CtOption<Self> {
+            <$point as crate::Point>::from_uncompressed_unchecked(bytes).map(Self)
         }
     };
 }
 
 macro_rules! cond_select_impl {
-    ($name:ident, $projective:ident) => {
-        impl subtle::ConditionallySelectable for $name {
+    ($name:ident $(<$s:ident>)?) => {
+        impl$(<$s: crate::Scheme>)? subtle::ConditionallySelectable for $name$(<$s>)? {
             fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
-                Self($projective::conditional_select(&a.0, &b.0, choice))
+                Self(subtle::ConditionallySelectable::conditional_select(
+                    &a.0, &b.0, choice,
+                ))
             }
         }
     };
 }
 
 macro_rules! serde_impl {
-    ($name:ident, $projective:ident) => {
+    ($name:ident<$s:ident>, $group:ident) => {
+        serde_impl!($name<$s>, $group, Bytes);
+    };
+    ($name:ident<$s:ident>, $group:ident, $bytes:ident) => {
+        impl<$s: crate::Scheme> crate::ByteEncoding for $name<$s> {
+            type Bytes = <$s::$group as crate::Point>::$bytes;
+
+            fn encode(&self) -> Self::Bytes {
+                self.to_bytes()
+            }
+
+            fn decode(bytes: &[u8]) -> Option<Self> {
+                Self::try_from_bytes(bytes).ok()
+            }
+        }
+
+        impl<$s: crate::Scheme> serde::Serialize for $name<$s> {
+            fn serialize<Ser>(&self, s: Ser) -> Result<Ser::Ok, Ser::Error>
+            where
+                Ser: serde::Serializer,
+            {
+                crate::serdes::serialize(self, s)
+            }
+        }
+
+        impl<'de, $s: crate::Scheme> serde::Deserialize<'de> for $name<$s> {
+            fn deserialize<D>(d: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                crate::serdes::deserialize(d)
+            }
+        }
+    };
+    ($name:ident) => {
+        impl crate::ByteEncoding for $name {
+            type Bytes = [u8; $name::BYTES];
+
+            fn encode(&self) -> Self::Bytes {
+                self.to_bytes()
+            }
+
+            fn decode(bytes: &[u8]) -> Option<Self> {
+                Self::try_from_bytes(bytes).ok()
+            }
+        }
+
         impl serde::Serialize for $name {
             fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
             where
                 S: serde::Serializer,
             {
-                self.0.serialize(s)
+                crate::serdes::serialize(self, s)
             }
         }
 
@@ -52,16 +198,47 @@ macro_rules! serde_impl {
             where
                 D: serde::Deserializer<'de>,
             {
-                let p = $projective::deserialize(d)?;
-                Ok(Self(p))
+                crate::serdes::deserialize(d)
             }
         }
     };
 }
 
-macro_rules! display_one_impl {
+macro_rules! uncompressed_impl {
+    ($name:ident<$s:ident>, $group:ident) => {
+        uncompressed_impl!($name<$s>, $group, UncompressedBytes);
+    };
+    ($name:ident<$s:ident>, $group:ident, $bytes:ident) => {
+        impl<$s: crate::Scheme> crate::UncompressedEncoding for $name<$s> {
+            type Bytes = <$s::$group as crate::Point>::$bytes;
+
+            fn encode_uncompressed(&self) -> Self::Bytes {
+                self.to_uncompressed_bytes()
+            }
+
+            fn decode_uncompressed(bytes: &[u8]) -> Option<Self> {
+                Self::try_from_uncompressed_bytes(bytes).ok()
+            }
+        }
+    };
     ($name:ident) => {
-        impl core::fmt::Display for $name {
+        impl crate::UncompressedEncoding for $name {
+            type Bytes = [u8; $name::UNCOMPRESSED_BYTES];
+
+            fn encode_uncompressed(&self) -> Self::Bytes {
+                self.to_uncompressed_bytes()
+            }
+
+            fn decode_uncompressed(bytes: &[u8]) -> Option<Self> {
+                Self::try_from_uncompressed_bytes(bytes).ok()
+            }
+        }
+    };
+}
+
+macro_rules! display_one_impl {
+    ($name:ident $(<$s:ident>)?) => {
+        impl$(<$s: crate::Scheme>)? core::fmt::Display for $name$(<$s>)? {
             fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
                 write!(f, "{}", self.0)
             }
@@ -69,6 +246,22 @@ macro_rules! display_one_impl {
     };
 }
 
+macro_rules! into_bytes_impl {
+    ($name:ident) => {
+        impl From<$name> for [u8; $name::BYTES] {
+            fn from(value: $name) -> Self {
+                value.to_bytes()
+            }
+        }
+
+        impl<'a> From<&'a $name> for [u8; $name::BYTES] {
+            fn from(value: &'a $name) -> [u8; $name::BYTES] {
+                value.to_bytes()
+            }
+        }
+    };
+}
+
 macro_rules! display_size_impl {
     ($name:ident, $size:expr) => {
         impl core::fmt::Display for $name {
@@ -0,0 +1,204 @@
+use crate::{HashedMessageOf, MinPk, MinSig, Point, PublicKeyOf, Scheme, SecretKey, SignatureOf};
+use bls12_381_plus::{ExpandMessageState, InitExpandMessage};
+use core::marker::PhantomData;
+use sha2::{Digest, Sha256};
+use subtle::Choice;
+
+/// Hashes a message to the signature group in chunks so it never needs to be held in memory.
+///
+/// The result is identical to hashing the concatenated chunks
+/// with [`SignatureOf::new`] or [`SignatureOf::verify`].
+#[derive(Clone, Debug)]
+pub struct MessageHasherOf<S: Scheme>(Sha256, PhantomData<S>);
+
+/// Hashes a message to G1 for [`crate::Signature`]
+pub type MessageHasher = MessageHasherOf<MinSig>;
+
+/// Hashes a message to G2 for [`crate::SignatureVt`]
+pub type MessageHasherVt = MessageHasherOf<MinPk>;
+
+impl<S: Scheme> Default for MessageHasherOf<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: Scheme> MessageHasherOf<S> {
+    /// The number of uniform bytes needed to hash to the signature group
+    const LEN_IN_BYTES: u16 = <S::SignatureGroup as Point>::HASH_LEN_IN_BYTES;
+
+    /// Start hashing a new message
+    pub fn new() -> Self {
+        Self(xmd_init(), PhantomData)
+    }
+
+    /// Add the next chunk of the message
+    pub fn update<B: AsRef<[u8]>>(&mut self, data: B) {
+        self.0.update(data.as_ref());
+    }
+
+    /// Add the next chunk of the message and return self
+    pub fn chain<B: AsRef<[u8]>>(mut self, data: B) -> Self {
+        self.update(data);
+        self
+    }
+
+    /// Finish hashing the message to the signature group
+    pub fn finalize(self) -> HashedMessageOf<S> {
+        let b_0 = xmd_b_0(self.0, Self::LEN_IN_BYTES, S::SIGNATURE_DST);
+        HashedMessageOf {
+            point: S::SignatureGroup::hash_to_curve::<ExpandMsgXmdB0>(&b_0, S::SIGNATURE_DST),
+            dst: S::SIGNATURE_DST,
+        }
+    }
+
+    /// Finish hashing the message and sign it
+    pub fn sign(self, sk: &SecretKey) -> Option<SignatureOf<S>> {
+        SignatureOf::new_prehashed(sk, &self.finalize())
+    }
+
+    /// Finish hashing the message and verify `sig` over it with `pk`
+    pub fn verify(self, sig: &SignatureOf<S>, pk: PublicKeyOf<S>) -> Choice {
+        sig.verify_prehashed(pk, &self.finalize())
+    }
+}
+
+/// Start expand_message_xmd by absorbing the zero padding block
+pub(crate) fn xmd_init() -> Sha256 {
+    Sha256::new().chain([0u8; 64])
+}
+
+/// Finish computing b_0 for expand_message_xmd once the message is absorbed
+pub(crate) fn xmd_b_0(hasher: Sha256, len_in_bytes: u16, dst: &[u8]) -> [u8; 32] {
+    hasher
+        .chain(len_in_bytes.to_be_bytes())
+        .chain([0u8])
+        .chain(dst)
+        .chain([dst.len() as u8])
+        .finalize()
+        .into()
+}
+
+/// expand_message_xmd with SHA-256 that takes b_0 in place of the message.
+///
+/// Hash to curve only gives the message to the expander in one slice,
+/// so the streamed message is reduced to b_0 first and passed through here.
+/// Only short domain separation tags are supported.
+pub(crate) struct ExpandMsgXmdB0;
+
+/// The state of [`ExpandMsgXmdB0`]
+pub(crate) struct ExpandMsgXmdB0State<'x> {
+    dst: &'x [u8],
+    b_0: [u8; 32],
+    b_i: [u8; 32],
+    i: u8,
+    offset: usize,
+    remain: usize,
+}
+
+impl<'x> InitExpandMessage<'x> for ExpandMsgXmdB0 {
+    type Expander = ExpandMsgXmdB0State<'x>;
+
+    fn init_expand(message: &[u8], dst: &'x [u8], len_in_bytes: usize) -> Self::Expander {
+        let b_0 = <[u8; 32]>::try_from(message).expect("b_0 is 32 bytes");
+        let b_1 = Sha256::new()
+            .chain(b_0)
+            .chain([1u8])
+            .chain(dst)
+            .chain([dst.len() as u8])
+            .finalize()
+            .into();
+        ExpandMsgXmdB0State {
+            dst,
+            b_0,
+            b_i: b_1,
+            i: 2,
+            offset: 0,
+            remain: len_in_bytes,
+        }
+    }
+}
+
+impl<'x> ExpandMessageState<'x> for ExpandMsgXmdB0State<'x> {
+    fn read_into(&mut self, output: &mut [u8]) -> usize {
+        let read_len = self.remain.min(output.len());
+        let mut offs = 0;
+        while offs < read_len {
+            if self.offset < self.b_i.len() {
+                let copy_len = (self.b_i.len() - self.offset).min(read_len - offs);
+                output[offs..offs + copy_len]
+                    .copy_from_slice(&self.b_i[self.offset..self.offset + copy_len]);
+                offs += copy_len;
+                self.offset += copy_len;
+            } else {
+                let mut b_prev_xor = self.b_0;
+                for (b, p) in b_prev_xor.iter_mut().zip(self.b_i.iter()) {
+                    *b ^= p;
+                }
+                self.b_i = Sha256::new()
+                    .chain(b_prev_xor)
+                    .chain([self.i])
+                    .chain(self.dst)
+                    .chain([self.dst.len() as u8])
+                    .finalize()
+                    .into();
+                self.offset = 0;
+                self.i += 1;
+            }
+        }
+        self.remain -= read_len;
+        read_len
+    }
+
+    fn remain(&self) -> usize {
+        self.remain
+    }
+}
+
+#[test]
+fn message_hasher_works() {
+    use crate::{HashedMessage, MockRng, PublicKey, Signature};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([43u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let msg = (0..1000u32).map(|i| i as u8).collect::<Vec<u8>>();
+
+    let mut hasher = MessageHasher::new();
+    for chunk in msg.chunks(77) {
+        hasher.update(chunk);
+    }
+    assert_eq!(hasher.clone().finalize(), HashedMessage::new(&msg));
+    let sig = hasher.clone().sign(&sk).unwrap();
+    assert_eq!(sig, Signature::new(&sk, &msg).unwrap());
+    assert_eq!(hasher.verify(&sig, pk).unwrap_u8(), 1u8);
+
+    assert_eq!(MessageHasher::new().finalize(), HashedMessage::new(b""));
+    let hasher = MessageHasher::new().chain(&msg[..500]).chain(&msg[501..]);
+    assert_eq!(hasher.verify(&sig, pk).unwrap_u8(), 0u8);
+}
+
+#[test]
+fn message_hasher_vt_works() {
+    use crate::{HashedMessageVt, MockRng, PublicKeyVt, SignatureVt};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([44u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKeyVt::from(&sk);
+    let msg = (0..1000u32).map(|i| i as u8).collect::<Vec<u8>>();
+
+    let mut hasher = MessageHasherVt::new();
+    for chunk in msg.chunks(77) {
+        hasher.update(chunk);
+    }
+    assert_eq!(hasher.clone().finalize(), HashedMessageVt::new(&msg));
+    let sig = hasher.clone().sign(&sk).unwrap();
+    assert_eq!(sig, SignatureVt::new(&sk, &msg).unwrap());
+    assert_eq!(hasher.verify(&sig, pk).unwrap_u8(), 1u8);
+
+    assert_eq!(MessageHasherVt::new().finalize(), HashedMessageVt::new(b""));
+    let hasher = MessageHasherVt::new().chain(&msg[..500]).chain(&msg[501..]);
+    assert_eq!(hasher.verify(&sig, pk).unwrap_u8(), 0u8);
+}
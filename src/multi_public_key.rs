@@ -1,33 +1,49 @@
-use crate::PublicKey;
-use bls12_381_plus::{G2Affine, G2Projective};
-use group::Curve;
+use crate::{MinPk, MinSig, PublicKeyOf, Scheme, Validated};
+use group::Group;
 use subtle::{Choice, CtOption};
 
 /// Represents multiple public keys into one that can be used to verify multisignatures
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub struct MultiPublicKey(pub G2Projective);
+pub struct MultiPublicKeyOf<S: Scheme>(pub S::PublicKeyGroup);
 
-impl From<&[PublicKey]> for MultiPublicKey {
-    fn from(keys: &[PublicKey]) -> Self {
-        let mut g = G2Projective::IDENTITY;
-        for k in keys {
-            g += k.0;
+/// Represents multiple public keys in G2 into one
+pub type MultiPublicKey = MultiPublicKeyOf<MinSig>;
+
+/// Represents multiple public keys in G1 into one
+pub type MultiPublicKeyVt = MultiPublicKeyOf<MinPk>;
+
+impl<S: Scheme> From<&[Validated<PublicKeyOf<S>>]> for MultiPublicKeyOf<S> {
+    fn from(keys: &[Validated<PublicKeyOf<S>>]) -> Self {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            Self(crate::parallel::sum(keys.par_iter().map(|k| k.as_ref().0)))
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            let mut g = S::PublicKeyGroup::identity();
+            for k in keys {
+                g += k.as_ref().0;
+            }
+            Self(g)
         }
-        Self(g)
     }
 }
 
-display_one_impl!(MultiPublicKey);
+display_one_impl!(MultiPublicKeyOf<S>);
+
+serde_impl!(MultiPublicKeyOf<S>, PublicKeyGroup);
 
-serde_impl!(MultiPublicKey, G2Projective);
+uncompressed_impl!(MultiPublicKeyOf<S>, PublicKeyGroup);
 
-cond_select_impl!(MultiPublicKey, G2Projective);
+cond_select_impl!(MultiPublicKeyOf<S>);
 
-impl MultiPublicKey {
+impl<S: Scheme> MultiPublicKeyOf<S> {
     /// Number of bytes needed to represent the multi public key
-    pub const BYTES: usize = 96;
+    pub const BYTES: usize = <S::PublicKeyGroup as crate::Point>::BYTES;
 
     validity_checks!();
 
-    bytes_impl!(G2Affine, G2Projective);
+    bytes_impl!(S::PublicKeyGroup);
 }
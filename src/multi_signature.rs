@@ -1,38 +1,96 @@
-use crate::{MultiPublicKey, PublicKey, Signature};
-use bls12_381_plus::{G1Affine, G1Projective};
-use group::Curve;
+use crate::{
+    HashedMessageOf, MinPk, MinSig, MultiPublicKeyOf, PreparedPublicKeyOf, PublicKeyOf, Scheme,
+    SignatureOf, Validated,
+};
+use group::Group;
 use subtle::{Choice, CtOption};
 
-/// Represents a BLS signature in G1 for multiple signatures that signed the same message
+/// Represents a BLS signature for multiple signatures that signed the same message
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub struct MultiSignature(pub G1Projective);
+pub struct MultiSignatureOf<S: Scheme>(pub S::SignatureGroup);
+
+/// Represents a BLS signature in G1 for multiple signatures that signed the same message
+pub type MultiSignature = MultiSignatureOf<MinSig>;
+
+/// Represents a BLS signature in G2 for multiple signatures that signed the same message
+pub type MultiSignatureVt = MultiSignatureOf<MinPk>;
 
-display_one_impl!(MultiSignature);
+display_one_impl!(MultiSignatureOf<S>);
 
-impl From<&[Signature]> for MultiSignature {
-    fn from(sigs: &[Signature]) -> Self {
-        let mut g = G1Projective::IDENTITY;
+impl<S: Scheme> From<&[Validated<SignatureOf<S>>]> for MultiSignatureOf<S> {
+    fn from(sigs: &[Validated<SignatureOf<S>>]) -> Self {
+        let mut g = S::SignatureGroup::identity();
         for s in sigs {
-            g += s.0;
+            g += s.as_ref().0;
         }
         Self(g)
     }
 }
 
-serde_impl!(MultiSignature, G1Projective);
+serde_impl!(MultiSignatureOf<S>, SignatureGroup);
 
-cond_select_impl!(MultiSignature, G1Projective);
+uncompressed_impl!(MultiSignatureOf<S>, SignatureGroup);
 
-impl MultiSignature {
+cond_select_impl!(MultiSignatureOf<S>);
+
+impl<S: Scheme> MultiSignatureOf<S> {
     /// Number of bytes needed to represent the signature
-    pub const BYTES: usize = 48;
+    pub const BYTES: usize = <S::SignatureGroup as crate::Point>::BYTES;
 
     validity_checks!();
 
-    bytes_impl!(G1Affine, G1Projective);
+    bytes_impl!(S::SignatureGroup);
+
+    /// Verify this multi signature is over `msg` with the multi public key
+    pub fn verify<B: AsRef<[u8]>>(&self, public_key: MultiPublicKeyOf<S>, msg: B) -> Choice {
+        SignatureOf::<S>(self.0).verify(PublicKeyOf(public_key.0), msg)
+    }
+
+    /// Verify this multi signature is over an already hashed `msg` with the multi public key
+    pub fn verify_prehashed(
+        &self,
+        public_key: MultiPublicKeyOf<S>,
+        msg: &HashedMessageOf<S>,
+    ) -> Choice {
+        SignatureOf::<S>(self.0).verify_prehashed(PublicKeyOf(public_key.0), msg)
+    }
 
     /// Verify this multi signature is over `msg` with the multi public key
-    pub fn verify<B: AsRef<[u8]>>(&self, public_key: MultiPublicKey, msg: B) -> Choice {
-        Signature(self.0).verify(PublicKey(public_key.0), msg)
+    /// and return the reason if it isn't
+    pub fn try_verify<B: AsRef<[u8]>>(
+        &self,
+        public_key: MultiPublicKeyOf<S>,
+        msg: B,
+    ) -> Result<(), crate::Error> {
+        SignatureOf::<S>(self.0).try_verify(PublicKeyOf(public_key.0), msg)
+    }
+
+    /// Verify this multi signature is over an already hashed `msg` with the multi public key
+    /// and return the reason if it isn't
+    pub fn try_verify_prehashed(
+        &self,
+        public_key: MultiPublicKeyOf<S>,
+        msg: &HashedMessageOf<S>,
+    ) -> Result<(), crate::Error> {
+        SignatureOf::<S>(self.0).try_verify_prehashed(PublicKeyOf(public_key.0), msg)
+    }
+
+    /// Verify this multi signature is over `msg` with a prepared multi public key
+    pub fn verify_prepared<B: AsRef<[u8]>>(
+        &self,
+        public_key: &PreparedPublicKeyOf<S>,
+        msg: B,
+    ) -> Choice {
+        SignatureOf::<S>(self.0).verify_prepared(public_key, msg)
+    }
+
+    /// Verify this multi signature is over `msg` with a prepared multi public key
+    /// and return the reason if it isn't
+    pub fn try_verify_prepared<B: AsRef<[u8]>>(
+        &self,
+        public_key: &PreparedPublicKeyOf<S>,
+        msg: B,
+    ) -> Result<(), crate::Error> {
+        SignatureOf::<S>(self.0).try_verify_prepared(public_key, msg)
     }
 }
@@ -0,0 +1,344 @@
+use crate::{
+    AggregateSignature, AggregateSignatureVt, ByteEncoding, Error, MultiSignature,
+    MultiSignatureVt, PartialSignature, PartialSignatureVt, PublicKey, PublicKeyOf, PublicKeyVt,
+    Scheme, SecretKey, SecretKeyShare, Signature, SignatureVt,
+};
+use alloc::{string::String, vec::Vec};
+use multibase::Base;
+
+/// The multicodec codes used to tag BLS12-381 keys and signatures.
+/// See <https://github.com/multiformats/multicodec/blob/master/table.csv>
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u64)]
+pub enum Multicodec {
+    /// `bls12_381-g1-pub`, a public key in G1
+    Bls12381G1Pub = 0xea,
+    /// `bls12_381-g2-pub`, a public key in G2
+    Bls12381G2Pub = 0xeb,
+    /// `bls12_381-g1-priv`, a secret key for a public key in G1
+    Bls12381G1Priv = 0x1309,
+    /// `bls12_381-g2-priv`, a secret key for a public key in G2
+    Bls12381G2Priv = 0x130a,
+    /// `bls12_381-g1-priv-share`, a secret key share for a public key in G1
+    Bls12381G1PrivShare = 0x130e,
+    /// `bls12_381-g2-priv-share`, a secret key share for a public key in G2
+    Bls12381G2PrivShare = 0x130f,
+    /// `bls12_381-g1-sig`, a signature in G1
+    Bls12381G1Sig = 0xd0ea,
+    /// `bls12_381-g2-sig`, a signature in G2
+    Bls12381G2Sig = 0xd0eb,
+}
+
+impl From<Multicodec> for u64 {
+    fn from(codec: Multicodec) -> Self {
+        codec as u64
+    }
+}
+
+impl TryFrom<u64> for Multicodec {
+    type Error = Error;
+
+    fn try_from(code: u64) -> Result<Self, Error> {
+        match code {
+            0xea => Ok(Self::Bls12381G1Pub),
+            0xeb => Ok(Self::Bls12381G2Pub),
+            0x1309 => Ok(Self::Bls12381G1Priv),
+            0x130a => Ok(Self::Bls12381G2Priv),
+            0x130e => Ok(Self::Bls12381G1PrivShare),
+            0x130f => Ok(Self::Bls12381G2PrivShare),
+            0xd0ea => Ok(Self::Bls12381G1Sig),
+            0xd0eb => Ok(Self::Bls12381G2Sig),
+            _ => Err(Error::UnknownCodec(code)),
+        }
+    }
+}
+
+/// The kind of signature in a multisig encoding,
+/// since several signature types share a multicodec.
+/// This is specific to this crate and not part of the multicodec table
+/// so other implementations won't read the multisigs produced here.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum SignatureKind {
+    /// A signature by one signer
+    Single = 0,
+    /// A signature from several signers over the same message
+    Multi = 1,
+    /// A signature from several signers over different messages
+    Aggregate = 2,
+    /// A signature share to be combined with others
+    Partial = 3,
+}
+
+/// Encode a key as a multikey, the unsigned varint multicodec followed by the key bytes
+pub trait Multikey: ByteEncoding {
+    /// The multicodec for this key
+    const CODEC: Multicodec;
+
+    /// Get the multikey bytes
+    fn to_multikey(&self) -> Vec<u8> {
+        let bytes = self.encode();
+        let mut out = Vec::with_capacity(bytes.as_ref().len() + 3);
+        write_varint(&mut out, Self::CODEC.into());
+        out.extend_from_slice(bytes.as_ref());
+        out
+    }
+
+    /// Check if `codec` is accepted when converting, by default only [`Self::CODEC`]
+    fn accepts(codec: Multicodec) -> bool {
+        codec == Self::CODEC
+    }
+
+    /// Convert the multikey bytes
+    fn from_multikey(bytes: &[u8]) -> Result<Self, Error> {
+        let (codec, rest) = read_codec(bytes)?;
+        if !Self::accepts(codec) {
+            return Err(Error::InvalidCodec {
+                expected: Self::CODEC.into(),
+                actual: codec.into(),
+            });
+        }
+        Self::decode(rest).ok_or(Error::InvalidEncoding)
+    }
+
+    /// Get the multikey as a multibase string in `base`
+    fn to_multibase(&self, base: Base) -> String {
+        multibase::encode(base, self.to_multikey())
+    }
+
+    /// Convert a multikey multibase string
+    fn from_multibase(s: &str) -> Result<Self, Error> {
+        let (_, bytes) = multibase::decode(s).map_err(|_| Error::InvalidEncoding)?;
+        Self::from_multikey(&bytes)
+    }
+}
+
+/// Encode a signature as a multisig, the unsigned varint multicodec
+/// then the [`SignatureKind`] as an unsigned varint followed by the signature bytes.
+/// The [`SignatureKind`] varint is specific to this crate.
+pub trait Multisig: ByteEncoding {
+    /// The multicodec for this signature
+    const CODEC: Multicodec;
+    /// The kind of signature
+    const KIND: SignatureKind;
+
+    /// Get the multisig bytes
+    fn to_multisig(&self) -> Vec<u8> {
+        let bytes = self.encode();
+        let mut out = Vec::with_capacity(bytes.as_ref().len() + 4);
+        write_varint(&mut out, Self::CODEC.into());
+        write_varint(&mut out, Self::KIND as u64);
+        out.extend_from_slice(bytes.as_ref());
+        out
+    }
+
+    /// Convert the multisig bytes
+    fn from_multisig(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes = check_codec(bytes, Self::CODEC)?;
+        let (kind, bytes) = read_varint(bytes)?;
+        if kind != Self::KIND as u64 {
+            return Err(Error::InvalidEncoding);
+        }
+        Self::decode(bytes).ok_or(Error::InvalidEncoding)
+    }
+
+    /// Get the multisig as a multibase string in `base`
+    fn to_multibase(&self, base: Base) -> String {
+        multibase::encode(base, self.to_multisig())
+    }
+
+    /// Convert a multisig multibase string
+    fn from_multibase(s: &str) -> Result<Self, Error> {
+        let (_, bytes) = multibase::decode(s).map_err(|_| Error::InvalidEncoding)?;
+        Self::from_multisig(&bytes)
+    }
+}
+
+macro_rules! multikey_impl {
+    ($name:ident, $codec:ident) => {
+        impl Multikey for $name {
+            const CODEC: Multicodec = Multicodec::$codec;
+        }
+    };
+    ($name:ident, $g1:ident, $g2:ident) => {
+        impl Multikey for $name {
+            const CODEC: Multicodec = Multicodec::$g2;
+
+            fn accepts(codec: Multicodec) -> bool {
+                codec == Multicodec::$g1 || codec == Multicodec::$g2
+            }
+        }
+
+        impl $name {
+            /// Get the multikey bytes tagged for the public key group of `S`.
+            /// [`Multikey::to_multikey`] always uses the G2 codec
+            /// and [`Multikey::from_multikey`] accepts either.
+            pub fn to_multikey_for<S: Scheme>(&self) -> Vec<u8>
+            where
+                PublicKeyOf<S>: Multikey,
+            {
+                let codec = if <PublicKeyOf<S> as Multikey>::CODEC == Multicodec::Bls12381G1Pub {
+                    Multicodec::$g1
+                } else {
+                    Multicodec::$g2
+                };
+                let bytes = self.encode();
+                let mut out = Vec::with_capacity(bytes.as_ref().len() + 3);
+                write_varint(&mut out, codec.into());
+                out.extend_from_slice(bytes.as_ref());
+                out
+            }
+
+            /// Get the multikey for the public key group of `S` as a multibase string in `base`
+            pub fn to_multibase_for<S: Scheme>(&self, base: Base) -> String
+            where
+                PublicKeyOf<S>: Multikey,
+            {
+                multibase::encode(base, self.to_multikey_for::<S>())
+            }
+        }
+    };
+}
+
+macro_rules! multisig_impl {
+    ($name:ident, $codec:ident, $kind:ident) => {
+        impl Multisig for $name {
+            const CODEC: Multicodec = Multicodec::$codec;
+            const KIND: SignatureKind = SignatureKind::$kind;
+        }
+    };
+}
+
+multikey_impl!(PublicKey, Bls12381G2Pub);
+multikey_impl!(PublicKeyVt, Bls12381G1Pub);
+multikey_impl!(SecretKey, Bls12381G1Priv, Bls12381G2Priv);
+multikey_impl!(SecretKeyShare, Bls12381G1PrivShare, Bls12381G2PrivShare);
+
+multisig_impl!(Signature, Bls12381G1Sig, Single);
+multisig_impl!(SignatureVt, Bls12381G2Sig, Single);
+multisig_impl!(MultiSignature, Bls12381G1Sig, Multi);
+multisig_impl!(MultiSignatureVt, Bls12381G2Sig, Multi);
+multisig_impl!(AggregateSignature, Bls12381G1Sig, Aggregate);
+multisig_impl!(AggregateSignatureVt, Bls12381G2Sig, Aggregate);
+multisig_impl!(PartialSignature, Bls12381G1Sig, Partial);
+multisig_impl!(PartialSignatureVt, Bls12381G2Sig, Partial);
+
+/// Read the multicodec
+fn read_codec(bytes: &[u8]) -> Result<(Multicodec, &[u8]), Error> {
+    let (code, rest) = read_varint(bytes)?;
+    Ok((Multicodec::try_from(code)?, rest))
+}
+
+/// Read the multicodec and check it's `codec`
+fn check_codec(bytes: &[u8], codec: Multicodec) -> Result<&[u8], Error> {
+    let (actual, rest) = read_codec(bytes)?;
+    if actual != codec {
+        return Err(Error::InvalidCodec {
+            expected: codec.into(),
+            actual: actual.into(),
+        });
+    }
+    Ok(rest)
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Read a minimally encoded unsigned varint of at most 9 bytes
+fn read_varint(bytes: &[u8]) -> Result<(u64, &[u8]), Error> {
+    let mut value = 0u64;
+    for (i, b) in bytes.iter().enumerate().take(9) {
+        value |= ((b & 0x7f) as u64) << (7 * i);
+        if b & 0x80 == 0 {
+            if *b == 0 && i > 0 {
+                return Err(Error::InvalidEncoding);
+            }
+            return Ok((value, &bytes[i + 1..]));
+        }
+    }
+    Err(Error::InvalidEncoding)
+}
+
+#[test]
+fn multiformats_works() {
+    use crate::MockRng;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([67u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let pk_vt = PublicKeyVt::from(&sk);
+
+    let bytes = pk.to_multikey();
+    assert_eq!(&bytes[..2], &[0xeb, 0x01]);
+    assert_eq!(PublicKey::from_multikey(&bytes).unwrap(), pk);
+    let s = pk.to_multibase(Base::Base58Btc);
+    assert!(s.starts_with("zUC7"));
+    assert_eq!(PublicKey::from_multibase(&s).unwrap(), pk);
+    assert!(matches!(
+        PublicKey::from_multikey(&pk_vt.to_multikey()),
+        Err(Error::InvalidCodec {
+            expected: 0xeb,
+            actual: 0xea
+        })
+    ));
+    let s = pk_vt.to_multibase(Base::Base64Url);
+    assert_eq!(PublicKeyVt::from_multibase(&s).unwrap(), pk_vt);
+    assert_eq!(
+        SecretKey::from_multibase(&sk.to_multibase(Base::Base58Btc)).unwrap(),
+        sk
+    );
+    let bytes = sk.to_multikey_for::<crate::MinPk>();
+    assert_eq!(&bytes[..2], &[0x89, 0x26]);
+    assert_eq!(SecretKey::from_multikey(&bytes).unwrap(), sk);
+    let bytes = sk.to_multikey_for::<crate::MinSig>();
+    assert_eq!(bytes, sk.to_multikey());
+    assert_eq!(&bytes[..2], &[0x8a, 0x26]);
+    assert_eq!(SecretKey::from_multikey(&bytes).unwrap(), sk);
+    assert_eq!(
+        SecretKey::from_multikey(&pk.to_multikey()),
+        Err(Error::InvalidCodec {
+            expected: 0x130a,
+            actual: 0xeb
+        })
+    );
+    let shares = sk.split::<MockRng, 2, 3>(&mut rng).unwrap();
+    let bytes = shares[0].to_multikey_for::<crate::MinPk>();
+    assert_eq!(&bytes[..2], &[0x8e, 0x26]);
+    assert_eq!(
+        SecretKeyShare::from_multikey(&bytes).unwrap().to_bytes(),
+        shares[0].to_bytes()
+    );
+    let s = shares[1].to_multibase_for::<crate::MinSig>(Base::Base58Btc);
+    assert_eq!(
+        SecretKeyShare::from_multibase(&s).unwrap().to_bytes(),
+        shares[1].to_bytes()
+    );
+
+    let msg = b"multiformats";
+    let sig = Signature::new(&sk, msg).unwrap();
+    let bytes = sig.to_multisig();
+    assert_eq!(&bytes[..4], &[0xea, 0xa1, 0x03, 0x00]);
+    assert_eq!(Signature::from_multisig(&bytes).unwrap(), sig);
+    let s = sig.to_multibase(Base::Base58Btc);
+    assert_eq!(Signature::from_multibase(&s).unwrap(), sig);
+    // Same codec but a different kind of signature
+    assert!(MultiSignature::from_multisig(&bytes).is_err());
+    assert!(matches!(
+        SignatureVt::from_multisig(&bytes),
+        Err(Error::InvalidCodec { .. })
+    ));
+    assert!(matches!(
+        PublicKey::from_multikey(&[0x80, 0x00]),
+        Err(Error::InvalidEncoding)
+    ));
+    assert!(matches!(
+        PublicKey::from_multikey(&[0x01]),
+        Err(Error::UnknownCodec(1))
+    ));
+}
@@ -0,0 +1,117 @@
+use crate::Scheme;
+use alloc::vec::Vec;
+use bls12_381_plus::{MillerLoopResult, Scalar};
+use group::Group;
+use rayon::prelude::*;
+use vsss_rs::Error;
+
+/// The number of items to give each thread so every thread gets one chunk
+pub(crate) fn chunk_size(len: usize) -> usize {
+    len.div_ceil(rayon::current_num_threads()).max(1)
+}
+
+/// Sum the points across threads
+pub(crate) fn sum<G: Group + Send>(points: impl IntoParallelIterator<Item = G>) -> G {
+    points.into_par_iter().reduce(G::identity, |a, b| a + b)
+}
+
+/// Run one miller loop per chunk of `terms` and combine the results.
+/// `pair` selects the pairing inputs from each term.
+pub(crate) fn multi_miller_loop<S: Scheme, T: Sync>(
+    terms: &[T],
+    pair: fn(&T) -> (&S::SignatureInput, &S::PublicKeyInput),
+) -> MillerLoopResult {
+    terms
+        .par_chunks(chunk_size(terms.len()))
+        .map(|chunk| {
+            let t = chunk
+                .iter()
+                .map(pair)
+                .collect::<Vec<(&S::SignatureInput, &S::PublicKeyInput)>>();
+            S::multi_miller_loop(t.as_slice())
+        })
+        .reduce(MillerLoopResult::default, |a, b| a + b)
+}
+
+/// Interpolate the shares at zero.
+/// Gives the same result as `Shamir::combine_shares_group`
+/// but computes each share's contribution on its own thread.
+pub(crate) fn combine_shares_group<G>(shares: &[(u8, G)]) -> Result<G, Error>
+where
+    G: Group<Scalar = Scalar> + Send + Sync,
+{
+    let mut ids = Vec::with_capacity(shares.len());
+    for (id, _) in shares {
+        if *id == 0 {
+            return Err(Error::SharingInvalidIdentifier);
+        }
+        if ids.contains(id) {
+            return Err(Error::SharingDuplicateIdentifier);
+        }
+        ids.push(*id);
+    }
+    Ok(sum(shares.par_iter().map(|(i, point)| {
+        let xi = Scalar::from(*i as u64);
+        let mut num = Scalar::ONE;
+        let mut den = Scalar::ONE;
+        for j in ids.iter().filter(|j| *j != i) {
+            let xj = Scalar::from(*j as u64);
+            num *= xj;
+            den *= xj - xi;
+        }
+        // identifiers are distinct so den is never zero
+        *point * (num * den.invert().unwrap())
+    })))
+}
+
+#[test]
+fn parallel_matches_serial() {
+    use crate::{MinSig, MockRng, SecretKey, Signature};
+    use bls12_381_plus::{G1Affine, G1Projective, G2Affine, G2Prepared};
+    use ff::Field;
+    use group::Curve;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([37u8; 16]);
+    let pairs = (0..9u8)
+        .map(|i| {
+            let sk = SecretKey::random(&mut rng).unwrap();
+            (
+                Signature::hash_msg(&[i]).to_affine(),
+                G2Prepared::from((G2Affine::generator() * sk.0).to_affine()),
+            )
+        })
+        .collect::<Vec<_>>();
+    let t = pairs
+        .iter()
+        .map(|(p1, p2)| (p1, p2))
+        .collect::<Vec<(&G1Affine, &G2Prepared)>>();
+    assert_eq!(
+        multi_miller_loop::<MinSig, _>(&pairs, |(p1, p2)| (p1, p2)).final_exponentiation(),
+        bls12_381_plus::multi_miller_loop(t.as_slice()).final_exponentiation()
+    );
+
+    let points = (0..9)
+        .map(|_| G1Projective::random(&mut rng))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sum(points.par_iter().copied()),
+        points.iter().fold(G1Projective::IDENTITY, |a, b| a + b)
+    );
+
+    let secret = Scalar::random(&mut rng);
+    let coeff = Scalar::random(&mut rng);
+    let shares = (1..=3u8)
+        .map(|i| {
+            (
+                i,
+                G1Projective::generator() * (secret + coeff * Scalar::from(i as u64)),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        combine_shares_group(&shares[1..]).unwrap(),
+        G1Projective::generator() * secret
+    );
+    assert!(combine_shares_group(&[shares[0], shares[0]]).is_err());
+}
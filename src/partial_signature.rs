@@ -1,64 +1,35 @@
-use crate::{SecretKeyShare, Signature};
-use bls12_381_plus::{G1Affine, G1Projective, Scalar};
-use group::Curve;
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::{
+    HashedMessageOf, MinPk, MinSig, Point, PublicKeyOf, Scheme, SecretKeyShare, SignatureOf,
+};
+use bls12_381_plus::Scalar;
+use group::Group;
 use subtle::Choice;
 use vsss_rs::Share;
 
-/// Represents a BLS partial signature in G1 using the proof of possession scheme
+/// Represents a BLS partial signature in the signature group of the scheme
+/// using the proof of possession scheme
 #[derive(Clone, Copy, Debug, Default)]
-pub struct PartialSignature(pub Share<PARTIAL_SIGNATURE_BYTES>);
-
-display_size_impl!(PartialSignature, PARTIAL_SIGNATURE_BYTES);
-
-impl From<Share<PARTIAL_SIGNATURE_BYTES>> for PartialSignature {
-    fn from(share: Share<PARTIAL_SIGNATURE_BYTES>) -> Self {
-        Self(share)
-    }
-}
-
-impl<'a> From<&'a Share<PARTIAL_SIGNATURE_BYTES>> for PartialSignature {
-    fn from(share: &'a Share<PARTIAL_SIGNATURE_BYTES>) -> Self {
-        Self(*share)
-    }
-}
-
-impl Serialize for PartialSignature {
-    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        self.0.serialize(s)
-    }
-}
+pub struct PartialSignatureOf<S: Scheme>(pub <S::SignatureGroup as Point>::Share);
 
-impl<'de> Deserialize<'de> for PartialSignature {
-    fn deserialize<D>(d: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let p = Share::<PARTIAL_SIGNATURE_BYTES>::deserialize(d)?;
-        Ok(Self(p))
-    }
-}
+/// Represents a BLS partial signature in G1 using the proof of possession scheme
+pub type PartialSignature = PartialSignatureOf<MinSig>;
 
-impl PartialSignature {
-    /// Number of bytes needed to represent the signature
-    pub const BYTES: usize = PARTIAL_SIGNATURE_BYTES;
+/// Represents a BLS partial signature in G2 using the proof of possession scheme
+pub type PartialSignatureVt = PartialSignatureOf<MinPk>;
 
+impl<S: Scheme> PartialSignatureOf<S> {
     /// Create a new bls
     pub fn new<B: AsRef<[u8]>>(sk: &SecretKeyShare, msg: B) -> Option<Self> {
         if sk.is_zero() {
             return None;
         }
-        let a = Signature::hash_msg(msg.as_ref());
+        let a = SignatureOf::<S>::hash_msg(msg.as_ref());
         let t = <[u8; 32]>::try_from(sk.0.value()).unwrap();
         let res = Scalar::from_bytes(&t).map(|s| {
-            let point = a * s;
-            let mut bytes = [0u8; PARTIAL_SIGNATURE_BYTES];
-            bytes[1..].copy_from_slice(&point.to_affine().to_compressed());
-            bytes[0] = sk.0.identifier();
-            Some(PartialSignature(Share(bytes)))
+            Some(Self(S::SignatureGroup::to_share(
+                sk.0.identifier(),
+                &(a * s),
+            )))
         });
         if res.is_some().unwrap_u8() == 1 {
             res.unwrap()
@@ -67,31 +38,126 @@ impl PartialSignature {
         }
     }
 
-    /// Check if this partial signature is valid
+    /// Create a new bls and return the reason if it fails
+    pub fn try_new<B: AsRef<[u8]>>(sk: &SecretKeyShare, msg: B) -> Result<Self, crate::Error> {
+        if sk.is_zero() {
+            return Err(crate::Error::ZeroKey);
+        }
+        Self::new(sk, msg).ok_or(crate::Error::InvalidEncoding)
+    }
+
+    /// Check if this partial signature is valid, i.e. the identifier is not zero
+    /// and the point is not the identity and is in the prime order subgroup
     pub fn is_valid(&self) -> Choice {
-        let t: [u8; 48] = <[u8; 48]>::try_from(self.0.value()).unwrap();
-        let p = G1Affine::from_compressed(&t).map(G1Projective::from);
-        p.map(|v| !v.is_identity() | v.is_on_curve())
+        let id = Choice::from((S::SignatureGroup::share_identifier(&self.0) != 0) as u8);
+        S::SignatureGroup::share_point(&self.0)
+            .map(|v| !v.is_identity())
             .unwrap_or_else(|| Choice::from(0u8))
+            & id
     }
 
     /// Check if this partial signature is invalid
     pub fn is_invalid(&self) -> Choice {
-        let t: [u8; 48] = <[u8; 48]>::try_from(self.0.value()).unwrap();
-        let p = G1Affine::from_compressed(&t).map(G1Projective::from);
-        p.map(|v| v.is_identity() | !v.is_on_curve())
+        !self.is_valid()
+    }
+
+    /// Check this partial signature is valid and return the reason if it isn't
+    pub fn validate(&self) -> Result<(), crate::Error> {
+        if S::SignatureGroup::share_identifier(&self.0) == 0 {
+            return Err(crate::Error::InvalidEncoding);
+        }
+        let p =
+            Option::<S::SignatureGroup>::from(S::SignatureGroup::share_point_unchecked(&self.0))
+                .ok_or(crate::Error::InvalidEncoding)?;
+        crate::Error::check_point(p.is_identity(), p.is_on_curve(), p.is_torsion_free())
+    }
+
+    /// Verify this partial signature is over `msg` with `pk`,
+    /// the public key of the secret key share that created it
+    pub fn verify<B: AsRef<[u8]>>(&self, pk: PublicKeyOf<S>, msg: B) -> Choice {
+        self.verify_prehashed(pk, &HashedMessageOf::new(msg))
+    }
+
+    /// Verify this partial signature is over an already hashed `msg` with `pk`
+    pub fn verify_prehashed(&self, pk: PublicKeyOf<S>, msg: &HashedMessageOf<S>) -> Choice {
+        if self.is_invalid().unwrap_u8() == 1u8 {
+            return Choice::from(0u8);
+        }
+        Option::<S::SignatureGroup>::from(S::SignatureGroup::share_point(&self.0))
+            .map(|p| SignatureOf::<S>(p).verify_prehashed(pk, msg))
             .unwrap_or_else(|| Choice::from(0u8))
     }
 
-    /// Get the byte sequence that represents this partial signature
-    pub fn to_bytes(self) -> [u8; Self::BYTES] {
-        self.0.0
+    /// Verify this partial signature is over `msg` with `pk`
+    /// and return the reason if it isn't
+    pub fn try_verify<B: AsRef<[u8]>>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+    ) -> Result<(), crate::Error> {
+        self.try_verify_prehashed(pk, &HashedMessageOf::new(msg))
     }
 
-    /// Convert a big-endian representation of the partial signature
-    pub fn from_bytes(bytes: &[u8; Self::BYTES]) -> Self {
-        Self(Share(*bytes))
+    /// Verify this partial signature is over an already hashed `msg` with `pk`
+    /// and return the reason if it isn't
+    pub fn try_verify_prehashed(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: &HashedMessageOf<S>,
+    ) -> Result<(), crate::Error> {
+        self.validate()?;
+        let p =
+            Option::<S::SignatureGroup>::from(S::SignatureGroup::share_point_unchecked(&self.0))
+                .ok_or(crate::Error::InvalidEncoding)?;
+        SignatureOf::<S>(p).try_verify_prehashed(pk, msg)
     }
 }
 
+macro_rules! partial_signature_impl {
+    ($name:ident, $bytes:ident) => {
+        display_size_impl!($name, $bytes);
+
+        impl From<Share<$bytes>> for $name {
+            fn from(share: Share<$bytes>) -> Self {
+                Self(share)
+            }
+        }
+
+        impl<'a> From<&'a Share<$bytes>> for $name {
+            fn from(share: &'a Share<$bytes>) -> Self {
+                Self(*share)
+            }
+        }
+
+        serde_impl!($name);
+
+        impl $name {
+            /// Number of bytes needed to represent the signature
+            pub const BYTES: usize = $bytes;
+
+            /// Get the byte sequence that represents this partial signature
+            pub fn to_bytes(self) -> [u8; Self::BYTES] {
+                self.0 .0
+            }
+
+            /// Convert a big-endian representation of the partial signature
+            pub fn from_bytes(bytes: &[u8; Self::BYTES]) -> Self {
+                Self(Share(*bytes))
+            }
+
+            /// Convert a big-endian representation of the partial signature
+            /// and return the reason if it's invalid
+            pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+                let sig = Self::from_bytes(crate::Error::check_length(bytes)?);
+                sig.validate()?;
+                Ok(sig)
+            }
+        }
+    };
+}
+
+partial_signature_impl!(PartialSignature, PARTIAL_SIGNATURE_BYTES);
+partial_signature_impl!(PartialSignatureVt, PARTIAL_SIGNATURE_VT_BYTES);
+
 pub(crate) const PARTIAL_SIGNATURE_BYTES: usize = 49;
+pub(crate) const PARTIAL_SIGNATURE_VT_BYTES: usize = 97;
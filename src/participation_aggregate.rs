@@ -0,0 +1,295 @@
+use crate::{
+    Error, MinPk, MinSig, MultiPublicKeyOf, MultiSignatureOf, PublicKeyOf, Scheme, SignatureOf,
+    Validated,
+};
+use alloc::vec::Vec;
+use serde::{Deserialize, Deserializer, Serialize};
+use subtle::Choice;
+
+/// A participation bitfield over an ordered, registered key set.
+/// Bit `i` is set when the key at index `i` contributed to the signature.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ParticipationBits {
+    /// The number of keys in the registered set
+    len: usize,
+    /// The packed bits, least significant bit first
+    bits: Vec<u8>,
+}
+
+impl ParticipationBits {
+    /// Create an empty bitfield for a key set of size `len`
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            bits: alloc::vec![0u8; len.div_ceil(8)],
+        }
+    }
+
+    /// The number of keys in the registered set
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Is the registered set empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Set the bit at `index`, returns false if out of range
+    pub fn set(&mut self, index: usize) -> bool {
+        if index >= self.len {
+            return false;
+        }
+        self.bits[index / 8] |= 1u8 << (index % 8);
+        true
+    }
+
+    /// Get the bit at `index`
+    pub fn get(&self, index: usize) -> bool {
+        if index >= self.len {
+            return false;
+        }
+        self.bits
+            .get(index / 8)
+            .is_some_and(|b| (b >> (index % 8)) & 1 == 1)
+    }
+
+    /// The number of participants
+    pub fn count(&self) -> usize {
+        self.bits.iter().map(|b| b.count_ones() as usize).sum()
+    }
+
+    /// Check whether two bitfields share any participants
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.bits
+            .iter()
+            .zip(other.bits.iter())
+            .any(|(a, b)| a & b != 0)
+    }
+
+    /// Iterate over the indices of all participants
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.len).filter(move |i| self.get(*i))
+    }
+
+    /// Check the bitfield is well-formed, i.e. no bits are set
+    /// beyond `len`
+    fn is_well_formed(&self) -> bool {
+        if self.bits.len() != self.len.div_ceil(8) {
+            return false;
+        }
+        match self.len % 8 {
+            0 => true,
+            r => self.bits[self.bits.len() - 1] >> r == 0,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ParticipationBits {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "ParticipationBits")]
+        struct Raw {
+            len: usize,
+            bits: Vec<u8>,
+        }
+
+        let Raw { len, bits } = Raw::deserialize(d)?;
+        let out = Self { len, bits };
+        if !out.is_well_formed() {
+            return Err(serde::de::Error::custom("malformed participation bits"));
+        }
+        Ok(out)
+    }
+}
+
+/// A multi-signature over a single message with a participation bitfield
+/// instead of an explicit list of public keys, as used by attestation style
+/// aggregation where the validator set is fixed and ordered.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(bound(
+    serialize = "MultiSignatureOf<S>: Serialize",
+    deserialize = "MultiSignatureOf<S>: Deserialize<'de>"
+))]
+pub struct ParticipationAggregateOf<S: Scheme> {
+    /// The aggregated signature
+    pub signature: MultiSignatureOf<S>,
+    /// The participants whose signatures are in `signature`
+    pub participants: ParticipationBits,
+}
+
+/// A multi-signature in G1 with a participation bitfield
+pub type ParticipationAggregate = ParticipationAggregateOf<MinSig>;
+
+/// A multi-signature in G2 with a participation bitfield
+pub type ParticipationAggregateVt = ParticipationAggregateOf<MinPk>;
+
+impl<S: Scheme> ParticipationAggregateOf<S> {
+    /// Create an aggregate containing the signature from the key
+    /// at `index` in a registered set of size `len`
+    pub fn new(signature: SignatureOf<S>, index: usize, len: usize) -> Option<Self> {
+        Self::try_new(signature, index, len).ok()
+    }
+
+    /// Create an aggregate containing the signature from the key
+    /// at `index` and return the reason if it fails
+    pub fn try_new(signature: SignatureOf<S>, index: usize, len: usize) -> Result<Self, Error> {
+        let mut participants = ParticipationBits::new(len);
+        if !participants.set(index) {
+            return Err(Error::InvalidIndex(index));
+        }
+        Ok(Self {
+            signature: MultiSignatureOf(signature.0),
+            participants,
+        })
+    }
+
+    /// Add a single signature from the key at `index`.
+    /// Returns `None` if `index` is out of range or already present.
+    pub fn add(&self, signature: SignatureOf<S>, index: usize) -> Option<Self> {
+        self.try_add(signature, index).ok()
+    }
+
+    /// Add a single signature from the key at `index`
+    /// and return the reason if it fails
+    pub fn try_add(&self, signature: SignatureOf<S>, index: usize) -> Result<Self, Error> {
+        let other = Self::try_new(signature, index, self.participants.len)?;
+        self.try_merge(&other)
+    }
+
+    /// Merge two aggregates over the same registered set.
+    /// Returns `None` if the sets differ in size or the participants overlap,
+    /// since the overlapping signatures would be counted twice.
+    pub fn merge(&self, other: &Self) -> Option<Self> {
+        self.try_merge(other).ok()
+    }
+
+    /// Merge two aggregates over the same registered set
+    /// and return the reason if it fails
+    pub fn try_merge(&self, other: &Self) -> Result<Self, Error> {
+        if self.participants.len != other.participants.len {
+            return Err(Error::InvalidLength {
+                expected: self.participants.len,
+                actual: other.participants.len,
+            });
+        }
+        if !self.participants.is_well_formed() || !other.participants.is_well_formed() {
+            return Err(Error::InvalidEncoding);
+        }
+        if let Some(i) = other
+            .participants
+            .iter()
+            .find(|i| self.participants.get(*i))
+        {
+            return Err(Error::DuplicateIndex(i));
+        }
+        let bits = self
+            .participants
+            .bits
+            .iter()
+            .zip(other.participants.bits.iter())
+            .map(|(a, b)| a | b)
+            .collect();
+        Ok(Self {
+            signature: MultiSignatureOf(self.signature.0 + other.signature.0),
+            participants: ParticipationBits {
+                len: self.participants.len,
+                bits,
+            },
+        })
+    }
+
+    /// Rebuild the multi public key from the participants in `keys`.
+    /// `keys` must be the registered set in order.
+    /// Returns `None` if any participant's key is invalid.
+    pub fn multi_public_key(&self, keys: &[PublicKeyOf<S>]) -> Option<MultiPublicKeyOf<S>> {
+        if keys.len() != self.participants.len
+            || !self.participants.is_well_formed()
+            || self.participants.count() == 0
+        {
+            return None;
+        }
+        let signers = self
+            .participants
+            .iter()
+            .map(|i| Validated::new(keys[i]).ok())
+            .collect::<Option<Vec<Validated<PublicKeyOf<S>>>>>()?;
+        Some(MultiPublicKeyOf::from(signers.as_slice()))
+    }
+
+    /// Verify this aggregate is over `msg` by the participants in `keys`.
+    /// `keys` must be the registered set in order.
+    pub fn verify<B: AsRef<[u8]>>(&self, keys: &[PublicKeyOf<S>], msg: B) -> Choice {
+        match self.multi_public_key(keys) {
+            Some(mpk) => self.signature.verify(mpk, msg),
+            None => Choice::from(0u8),
+        }
+    }
+}
+
+#[test]
+fn participation_aggregate_works() {
+    use crate::{MockRng, PublicKey, PublicKeyVt, SecretKey, Signature, SignatureVt};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([5u8; 16]);
+    let sks = (0..5)
+        .map(|_| SecretKey::random(&mut rng).unwrap())
+        .collect::<Vec<_>>();
+    let pks = sks.iter().map(PublicKey::from).collect::<Vec<_>>();
+    let msg = b"participation_aggregate";
+
+    let sig0 = Signature::new(&sks[0], msg).unwrap();
+    let sig2 = Signature::new(&sks[2], msg).unwrap();
+    let sig3 = Signature::new(&sks[3], msg).unwrap();
+
+    let a = ParticipationAggregate::new(sig0, 0, pks.len()).unwrap();
+    let b = ParticipationAggregate::new(sig2, 2, pks.len())
+        .unwrap()
+        .add(sig3, 3)
+        .unwrap();
+    let ab = a.merge(&b).unwrap();
+    assert_eq!(ab.participants.count(), 3);
+    assert_eq!(ab.verify(&pks, msg).unwrap_u8(), 1u8);
+    assert_eq!(ab.verify(&pks, b"another message").unwrap_u8(), 0u8);
+    assert_eq!(ab.verify(&pks[..4], msg).unwrap_u8(), 0u8);
+
+    // Overlapping participants are rejected
+    assert!(ab.merge(&b).is_none());
+    assert!(a.add(sig0, 0).is_none());
+    assert!(ParticipationAggregate::new(sig0, 5, pks.len()).is_none());
+
+    let ab_bytes = serde_bare::to_vec(&ab).unwrap();
+    let de_ab = serde_bare::from_slice::<ParticipationAggregate>(&ab_bytes).unwrap();
+    assert_eq!(de_ab, ab);
+
+    // Truncated or overlong bitfields are rejected
+    let short = ParticipationBits {
+        len: 20,
+        bits: alloc::vec![0xffu8],
+    };
+    let short_bytes = serde_bare::to_vec(&short).unwrap();
+    assert!(serde_bare::from_slice::<ParticipationBits>(&short_bytes).is_err());
+    assert!(!short.get(19));
+    let extra = ParticipationBits {
+        len: 4,
+        bits: alloc::vec![0xf0u8],
+    };
+    let extra_bytes = serde_bare::to_vec(&extra).unwrap();
+    assert!(serde_bare::from_slice::<ParticipationBits>(&extra_bytes).is_err());
+
+    let pks_vt = sks.iter().map(PublicKeyVt::from).collect::<Vec<_>>();
+    let a = ParticipationAggregateVt::new(SignatureVt::new(&sks[1], msg).unwrap(), 1, 5)
+        .unwrap()
+        .add(SignatureVt::new(&sks[4], msg).unwrap(), 4)
+        .unwrap();
+    assert_eq!(a.verify(&pks_vt, msg).unwrap_u8(), 1u8);
+    let a_bytes = serde_bare::to_vec(&a).unwrap();
+    assert_eq!(
+        serde_bare::from_slice::<ParticipationAggregateVt>(&a_bytes).unwrap(),
+        a
+    );
+}
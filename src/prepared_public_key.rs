@@ -0,0 +1,150 @@
+use crate::{MinPk, MinSig, MultiPublicKeyOf, Point, PublicKeyOf, Scheme, Validated};
+
+/// A public key prepared for repeated verification.
+///
+/// Caches the affine form of the key and its Miller loop input so
+/// verifying many signatures against the same key doesn't recompute them
+/// each time. The negated generator is shared through [`Scheme::with_neg_generator`].
+///
+/// Prepared keys can only be created from [`Validated`] keys so
+/// verifying with them skips the subgroup check of the key.
+#[derive(Clone, Debug)]
+pub struct PreparedPublicKeyOf<S: Scheme> {
+    pub(crate) key: PublicKeyOf<S>,
+    pub(crate) affine: <S::PublicKeyGroup as Point>::Affine,
+    pub(crate) prepared: S::PublicKeyInput,
+}
+
+/// A public key in G2 prepared for repeated verification
+pub type PreparedPublicKey = PreparedPublicKeyOf<MinSig>;
+
+/// A public key in G1 prepared for repeated verification
+pub type PreparedPublicKeyVt = PreparedPublicKeyOf<MinPk>;
+
+impl<S: Scheme> From<Validated<PublicKeyOf<S>>> for PreparedPublicKeyOf<S> {
+    fn from(key: Validated<PublicKeyOf<S>>) -> Self {
+        Self::new(key.into_inner())
+    }
+}
+
+impl<'a, S: Scheme> From<&'a Validated<PublicKeyOf<S>>> for PreparedPublicKeyOf<S> {
+    fn from(key: &'a Validated<PublicKeyOf<S>>) -> Self {
+        Self::from(*key)
+    }
+}
+
+impl<S: Scheme> From<Validated<MultiPublicKeyOf<S>>> for PreparedPublicKeyOf<S> {
+    fn from(key: Validated<MultiPublicKeyOf<S>>) -> Self {
+        Self::new(PublicKeyOf(key.into_inner().0))
+    }
+}
+
+impl<S: Scheme> PreparedPublicKeyOf<S> {
+    fn new(key: PublicKeyOf<S>) -> Self {
+        Self {
+            key,
+            affine: key.0.affine(),
+            prepared: S::public_key_input(&key.0),
+        }
+    }
+
+    /// The public key that was prepared
+    pub fn public_key(&self) -> PublicKeyOf<S> {
+        self.key
+    }
+
+    /// The affine form of the public key
+    pub fn affine(&self) -> <S::PublicKeyGroup as Point>::Affine {
+        self.affine
+    }
+}
+
+#[test]
+fn prepared_public_key_works() {
+    use crate::*;
+    use bls12_381_plus::Scalar;
+    use ff::Field;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([37u8; 16]);
+    let sk1 = SecretKey::random(&mut rng).unwrap();
+    let sk2 = SecretKey::random(&mut rng).unwrap();
+    let pk1 = PreparedPublicKey::from(Validated::new(PublicKey::from(&sk1)).unwrap());
+    let pk2 = PreparedPublicKey::from(Validated::new(PublicKey::from(&sk2)).unwrap());
+    let msg = b"prepared_public_key";
+
+    let sig1 = Signature::new(&sk1, msg).unwrap();
+    let sig2 = Signature::new(&sk2, b"another message").unwrap();
+    assert_eq!(sig1.verify_prepared(&pk1, msg).unwrap_u8(), 1u8);
+    assert_eq!(sig1.verify_prepared(&pk2, msg).unwrap_u8(), 0u8);
+
+    let valid1 = Validated::new(sig1).unwrap();
+    let valid2 = Validated::new(Signature::new(&sk2, msg).unwrap()).unwrap();
+    let msig = MultiSignature::from(&[valid1, valid2][..]);
+    let keys = [pk1.public_key(), pk2.public_key()].map(|k| Validated::new(k).unwrap());
+    let mpk = MultiPublicKey::from(&keys[..]);
+    let mpk = PreparedPublicKey::from(Validated::new(mpk).unwrap());
+    assert_eq!(msig.verify_prepared(&mpk, msg).unwrap_u8(), 1u8);
+
+    let asig = AggregateSignature::from(&[valid1, Validated::new(sig2).unwrap()][..]);
+    let data = [(&pk1, &msg[..]), (&pk2, &b"another message"[..])];
+    assert_eq!(asig.verify_prepared(&data).unwrap_u8(), 1u8);
+    let data = [(&pk2, &msg[..]), (&pk1, &b"another message"[..])];
+    assert_eq!(asig.verify_prepared(&data).unwrap_u8(), 0u8);
+
+    let pop = ProofOfPossession::new(&sk1).unwrap();
+    assert_eq!(pop.verify_prepared(&pk1).unwrap_u8(), 1u8);
+    assert_eq!(pop.verify_prepared(&pk2).unwrap_u8(), 0u8);
+
+    let y = Scalar::random(&mut rng);
+    let proof = sig1
+        .proof_of_knowledge(msg, Scalar::random(&mut rng), y)
+        .unwrap();
+    assert_eq!(proof.verify_prepared(&pk1, msg, y).unwrap_u8(), 1u8);
+    assert_eq!(proof.verify_prepared(&pk2, msg, y).unwrap_u8(), 0u8);
+}
+
+#[test]
+fn prepared_public_key_vt_works() {
+    use crate::*;
+    use bls12_381_plus::Scalar;
+    use ff::Field;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([37u8; 16]);
+    let sk1 = SecretKey::random(&mut rng).unwrap();
+    let sk2 = SecretKey::random(&mut rng).unwrap();
+    let pk1 = PreparedPublicKeyVt::from(Validated::new(PublicKeyVt::from(&sk1)).unwrap());
+    let pk2 = PreparedPublicKeyVt::from(Validated::new(PublicKeyVt::from(&sk2)).unwrap());
+    let msg = b"prepared_public_key";
+
+    let sig1 = SignatureVt::new(&sk1, msg).unwrap();
+    let sig2 = SignatureVt::new(&sk2, b"another message").unwrap();
+    assert_eq!(sig1.verify_prepared(&pk1, msg).unwrap_u8(), 1u8);
+    assert_eq!(sig1.verify_prepared(&pk2, msg).unwrap_u8(), 0u8);
+
+    let valid1 = Validated::new(sig1).unwrap();
+    let valid2 = Validated::new(SignatureVt::new(&sk2, msg).unwrap()).unwrap();
+    let msig = MultiSignatureVt::from(&[valid1, valid2][..]);
+    let keys = [pk1.public_key(), pk2.public_key()].map(|k| Validated::new(k).unwrap());
+    let mpk = MultiPublicKeyVt::from(&keys[..]);
+    let mpk = PreparedPublicKeyVt::from(Validated::new(mpk).unwrap());
+    assert_eq!(msig.verify_prepared(&mpk, msg).unwrap_u8(), 1u8);
+
+    let asig = AggregateSignatureVt::from(&[valid1, Validated::new(sig2).unwrap()][..]);
+    let data = [(&pk1, &msg[..]), (&pk2, &b"another message"[..])];
+    assert_eq!(asig.verify_prepared(&data).unwrap_u8(), 1u8);
+    let data = [(&pk2, &msg[..]), (&pk1, &b"another message"[..])];
+    assert_eq!(asig.verify_prepared(&data).unwrap_u8(), 0u8);
+
+    let pop = ProofOfPossessionVt::new(&sk1).unwrap();
+    assert_eq!(pop.verify_prepared(&pk1).unwrap_u8(), 1u8);
+    assert_eq!(pop.verify_prepared(&pk2).unwrap_u8(), 0u8);
+
+    let y = Scalar::random(&mut rng);
+    let proof = sig1
+        .proof_of_knowledge(msg, Scalar::random(&mut rng), y)
+        .unwrap();
+    assert_eq!(proof.verify_prepared(&pk1, msg, y).unwrap_u8(), 1u8);
+    assert_eq!(proof.verify_prepared(&pk2, msg, y).unwrap_u8(), 0u8);
+}
@@ -1,84 +1,274 @@
-use crate::{PublicKey, Signature};
-use bls12_381_plus::{multi_miller_loop, G1Projective, G2Affine, G2Prepared, Scalar};
+use crate::{Clock, MinPk, MinSig, Point, PreparedPublicKeyOf, PublicKeyOf, Scheme, SignatureOf};
+use bls12_381_plus::Scalar;
 use core::fmt::{self, Display, Formatter};
 use ff::Field;
-use group::{Curve, Group};
+use group::Group;
 use serde::{Deserialize, Serialize};
-use subtle::{Choice, ConditionallySelectable};
+use subtle::{Choice, ConditionallySelectable, CtOption};
 
 /// A signature proof of knowledge
-#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize, Serialize)]
-pub struct ProofOfKnowledge {
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ProofOfKnowledgeOf<S: Scheme> {
     /// x \cdot A
-    pub u: G1Projective,
+    pub u: S::SignatureGroup,
     /// V = -(x + y)((s - \alpha)\cdot A + \alpha \cdot A)
-    pub v: G1Projective,
+    pub v: S::SignatureGroup,
 }
 
-impl Display for ProofOfKnowledge {
+/// A signature proof of knowledge in G1 for [`crate::Signature`]
+pub type ProofOfKnowledge = ProofOfKnowledgeOf<MinSig>;
+
+/// A signature proof of knowledge in G2 for [`crate::SignatureVt`]
+pub type ProofOfKnowledgeVt = ProofOfKnowledgeOf<MinPk>;
+
+impl<S: Scheme> Display for ProofOfKnowledgeOf<S> {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{{ u: {}, v: {} }}", self.u, self.v)
     }
 }
 
-impl ConditionallySelectable for ProofOfKnowledge {
+impl<S: Scheme> ConditionallySelectable for ProofOfKnowledgeOf<S> {
     fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
         Self {
-            u: G1Projective::conditional_select(&a.u, &b.u, choice),
-            v: G1Projective::conditional_select(&a.v, &b.v, choice),
+            u: S::SignatureGroup::conditional_select(&a.u, &b.u, choice),
+            v: S::SignatureGroup::conditional_select(&a.v, &b.v, choice),
         }
     }
 }
 
-impl ProofOfKnowledge {
-    /// Check if this is valid
+impl<S: Scheme> ProofOfKnowledgeOf<S> {
+    /// Check if this is valid, i.e. both points are not the identity,
+    /// are on the curve and are in the prime order subgroup
     pub fn is_valid(&self) -> Choice {
-        !self.u.is_identity() | self.u.is_on_curve() | !self.v.is_identity() | self.v.is_on_curve()
+        let mut res = Choice::from(1u8);
+        for p in [self.u, self.v] {
+            res &= !p.is_identity() & p.is_on_curve() & p.is_torsion_free();
+        }
+        res
     }
 
     /// Check if this is invalid
     pub fn is_invalid(&self) -> Choice {
-        self.u.is_identity() | !self.u.is_on_curve() | self.v.is_identity() | !self.v.is_on_curve()
+        !self.is_valid()
     }
 
     /// Verify the proof of knowledge
-    pub fn verify<B: AsRef<[u8]>>(&self, pk: PublicKey, msg: B, y: Scalar) -> Choice {
+    pub fn verify<B: AsRef<[u8]>>(&self, pk: PublicKeyOf<S>, msg: B, y: Scalar) -> Choice {
         if (self.is_invalid() | pk.is_invalid()).unwrap_u8() == 1u8 {
             return 0u8.into();
         }
         if y.is_zero().unwrap_u8() == 1u8 {
             return 0u8.into();
         }
-        let a = Signature::hash_msg(msg.as_ref());
-        let g2 = G2Affine::generator();
+        let a = SignatureOf::<S>::hash_msg(msg.as_ref());
+        let g = S::PublicKeyGroup::generator();
         let uay = self.u + a * y;
 
-        multi_miller_loop(&[
-            (&self.v.to_affine(), &G2Prepared::from(g2)),
-            (&uay.to_affine(), &G2Prepared::from(pk.0.to_affine())),
+        S::pairing_check(&[
+            (&S::signature_input(&self.v), &S::public_key_input(&g)),
+            (&S::signature_input(&uay), &S::public_key_input(&pk.0)),
         ])
-        .final_exponentiation()
-        .is_identity()
     }
 
-    #[cfg(feature = "iso8601-timestamp")]
-    pub(crate) fn generate_timestamp_based_y(u: G1Projective) -> (Scalar, i64) {
-        let t = iso8601_timestamp::Timestamp::now_utc()
-            .duration_since(iso8601_timestamp::Timestamp::UNIX_EPOCH)
-            .whole_milliseconds() as i64;
+    /// Check both points are valid and return the reason if they aren't
+    pub fn validate(&self) -> Result<(), crate::Error> {
+        for p in [self.u, self.v] {
+            crate::Error::check_point(p.is_identity(), p.is_on_curve(), p.is_torsion_free())?;
+        }
+        Ok(())
+    }
+
+    /// Check a batch of proofs, e.g. those loaded with the unchecked
+    /// byte conversions, and return the index and reason of the first invalid one
+    pub fn validate_batch(items: &[Self]) -> Result<(), (usize, crate::Error)> {
+        for (i, item) in items.iter().enumerate() {
+            item.validate().map_err(|e| (i, e))?;
+        }
+        Ok(())
+    }
+
+    /// Verify the proof works and return the reason if it doesn't
+    pub fn try_verify<B: AsRef<[u8]>>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+        y: Scalar,
+    ) -> Result<(), crate::Error> {
+        pk.validate()?;
+        self.validate()?;
+        if y.is_zero().unwrap_u8() == 1u8 {
+            return Err(crate::Error::ZeroScalar);
+        }
+        crate::Error::verified(self.verify(pk, msg, y))
+    }
+
+    /// Verify the proof of knowledge with a prepared public key
+    pub fn verify_prepared<B: AsRef<[u8]>>(
+        &self,
+        pk: &PreparedPublicKeyOf<S>,
+        msg: B,
+        y: Scalar,
+    ) -> Choice {
+        if self.is_invalid().unwrap_u8() == 1u8 {
+            return 0u8.into();
+        }
+        if y.is_zero().unwrap_u8() == 1u8 {
+            return 0u8.into();
+        }
+        let a = SignatureOf::<S>::hash_msg(msg.as_ref());
+        let uay = self.u + a * y;
+
+        // e(v, g) == e(-v, -g) so the shared negated generator can be used
+        S::with_neg_generator(|neg_generator| {
+            S::pairing_check(&[
+                (&S::signature_input(&-self.v), neg_generator),
+                (&S::signature_input(&uay), &pk.prepared),
+            ])
+        })
+    }
+
+    /// Verify the proof of knowledge with a prepared public key
+    /// and return the reason if it doesn't
+    pub fn try_verify_prepared<B: AsRef<[u8]>>(
+        &self,
+        pk: &PreparedPublicKeyOf<S>,
+        msg: B,
+        y: Scalar,
+    ) -> Result<(), crate::Error> {
+        self.validate()?;
+        if y.is_zero().unwrap_u8() == 1u8 {
+            return Err(crate::Error::ZeroScalar);
+        }
+        crate::Error::verified(self.verify_prepared(pk, msg, y))
+    }
+
+    /// Verify many proofs of knowledge at once with a single multi-pairing.
+    /// Each proof is weighted by a random scalar from `rng` so the batch
+    /// only verifies if every proof in it is valid.
+    /// Each entry is the proof, the public key, the message and the challenge `y`.
+    #[cfg(feature = "alloc")]
+    pub fn batch_verify<B: AsRef<[u8]>>(
+        proofs: &[(Self, PublicKeyOf<S>, B, Scalar)],
+        mut rng: impl rand_core::RngCore + rand_core::CryptoRng,
+    ) -> Choice {
+        use alloc::vec::Vec;
+
+        if proofs.is_empty() {
+            return 0u8.into();
+        }
+        let mut inputs = Vec::with_capacity(proofs.len());
+        for (proof, pk, msg, y) in proofs {
+            if (proof.is_invalid() | pk.is_invalid() | y.is_zero()).unwrap_u8() == 1u8 {
+                return 0u8.into();
+            }
+            // Borrow the message so `B` doesn't need to be `Sync`
+            // and hash it to the curve with the rest of the entry
+            inputs.push((proof, pk, msg.as_ref(), *y, Scalar::random(&mut rng)));
+        }
+        let entry = |(proof, pk, msg, y, r): &(&Self, &PublicKeyOf<S>, &[u8], Scalar, Scalar)| {
+            let a = SignatureOf::<S>::hash_msg(msg);
+            (
+                S::signature_input(&((proof.u + a * y) * r)),
+                S::public_key_input(&pk.0),
+                proof.v * r,
+            )
+        };
+        let g = S::public_key_input(&S::PublicKeyGroup::generator());
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            let entries = inputs.par_iter().map(entry).collect::<Vec<_>>();
+            let v = crate::parallel::sum(entries.par_iter().map(|(_, _, v)| *v));
+            let v = S::signature_input(&v);
+            (crate::parallel::multi_miller_loop::<S, _>(&entries, |(a, pk, _)| (a, pk))
+                + S::multi_miller_loop(&[(&v, &g)]))
+            .final_exponentiation()
+            .is_identity()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            let entries = inputs.iter().map(entry).collect::<Vec<_>>();
+            let mut v = S::SignatureGroup::identity();
+            for (_, _, p) in &entries {
+                v += p;
+            }
+            let v = S::signature_input(&v);
+            let mut t = entries
+                .iter()
+                .map(|(a, pk, _)| (a, pk))
+                .collect::<Vec<(&S::SignatureInput, &S::PublicKeyInput)>>();
+            t.push((&v, &g));
+            S::pairing_check(t.as_slice())
+        }
+    }
+
+    /// Verify a non-interactive proof of knowledge created with
+    /// `proof_of_knowledge_with_context` using the same `context`
+    pub fn verify_with_context<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+        context: C,
+    ) -> Choice {
+        let msg = msg.as_ref();
+        let y = Self::compute_context_y(self.u, pk, msg, context.as_ref());
+        self.verify(pk, msg, y)
+    }
+
+    /// Verify a non-interactive proof of knowledge created with
+    /// `proof_of_knowledge_with_context` and return the reason if it doesn't
+    pub fn try_verify_with_context<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+        context: C,
+    ) -> Result<(), crate::Error> {
+        let msg = msg.as_ref();
+        let y = Self::compute_context_y(self.u, pk, msg, context.as_ref());
+        self.try_verify(pk, msg, y)
+    }
+
+    pub(crate) fn compute_context_y(
+        u: S::SignatureGroup,
+        pk: PublicKeyOf<S>,
+        msg: &[u8],
+        context: &[u8],
+    ) -> Scalar {
+        const INFO: [u8; 2] = [0u8, 48u8];
+
+        let mut extractor = hkdf::HkdfExtract::<sha2::Sha256>::new(Some(S::POK_CONTEXT_DST));
+        extractor.input_ikm(u.to_compressed().as_ref());
+        extractor.input_ikm(pk.to_bytes().as_ref());
+        // Length prefixes keep the message and context unambiguous
+        extractor.input_ikm(&(msg.len() as u64).to_le_bytes());
+        extractor.input_ikm(msg);
+        extractor.input_ikm(&(context.len() as u64).to_le_bytes());
+        extractor.input_ikm(context);
+        extractor.input_ikm(&[0u8]);
+        let (_, h) = extractor.finalize();
+
+        let mut output = [0u8; 48];
+        // 48 bytes is acceptable length so `unwrap` is okay
+        h.expand(&INFO, &mut output).unwrap();
+        Scalar::from_okm(&output)
+    }
+
+    pub(crate) fn generate_timestamp_based_y<C: Clock>(
+        u: S::SignatureGroup,
+        clock: &C,
+    ) -> (Scalar, i64) {
+        let t = clock.now_ms();
         (Self::compute_y(u, t), t)
     }
 
-    #[cfg(feature = "iso8601-timestamp")]
-    pub(crate) fn compute_y(u: G1Projective, t: i64) -> Scalar {
-        const DST: &[u8] = b"BLS12381G1-SIG-PROOF-OF-KNOWLEDGE-TIMESTAMP-";
+    pub(crate) fn compute_y(u: S::SignatureGroup, t: i64) -> Scalar {
         const INFO: [u8; 2] = [0u8, 48u8];
 
-        let mut y_bytes = [0u8; 56];
-        y_bytes[..48].copy_from_slice(&u.to_affine().to_compressed());
-        y_bytes[48..].copy_from_slice(&t.to_le_bytes());
-        let mut extractor = hkdf::HkdfExtract::<sha2::Sha256>::new(Some(DST));
-        extractor.input_ikm(&y_bytes);
+        let mut extractor = hkdf::HkdfExtract::<sha2::Sha256>::new(Some(S::POK_TIMESTAMP_DST));
+        extractor.input_ikm(u.to_compressed().as_ref());
+        extractor.input_ikm(&t.to_le_bytes());
         extractor.input_ikm(&[0u8]);
         let (_, h) = extractor.finalize();
 
@@ -89,32 +279,174 @@ impl ProofOfKnowledge {
     }
 }
 
-#[cfg(feature = "iso8601-timestamp")]
+serde_impl!(ProofOfKnowledgeOf<S>, SignatureGroup, PairBytes);
+
+uncompressed_impl!(ProofOfKnowledgeOf<S>, SignatureGroup, UncompressedPairBytes);
+
+impl<S: Scheme> ProofOfKnowledgeOf<S> {
+    /// Number of bytes needed to represent the proof
+    pub const BYTES: usize = <S::SignatureGroup as Point>::BYTES * 2;
+    /// Number of bytes needed to represent the proof with uncompressed points
+    pub const UNCOMPRESSED_BYTES: usize = Self::BYTES * 2;
+
+    /// Get the byte representation
+    pub fn to_bytes(self) -> <S::SignatureGroup as Point>::PairBytes {
+        Point::pair_to_compressed(&self.u, &self.v)
+    }
+
+    /// Convert a big-endian representation
+    pub fn from_bytes(bytes: &<S::SignatureGroup as Point>::PairBytes) -> CtOption<Self> {
+        let (u, v) = <S::SignatureGroup as Point>::split_pair(bytes);
+        <S::SignatureGroup as Point>::from_compressed(&u)
+            .and_then(|u| <S::SignatureGroup as Point>::from_compressed(&v).map(|v| Self { u, v }))
+    }
+
+    /// Convert a big-endian representation without the prime order subgroup check.
+    ///
+    /// **Only use this with trusted input.** The result can be checked
+    /// later with `validate` or `validate_batch`.
+    pub fn from_bytes_unchecked(bytes: &<S::SignatureGroup as Point>::PairBytes) -> CtOption<Self> {
+        let (u, v) = <S::SignatureGroup as Point>::split_pair(bytes);
+        <S::SignatureGroup as Point>::from_compressed_unchecked(&u).and_then(|u| {
+            <S::SignatureGroup as Point>::from_compressed_unchecked(&v).map(|v| Self { u, v })
+        })
+    }
+
+    /// Get the uncompressed byte representation
+    pub fn to_uncompressed_bytes(self) -> <S::SignatureGroup as Point>::UncompressedPairBytes {
+        Point::pair_to_uncompressed(&self.u, &self.v)
+    }
+
+    /// Convert an uncompressed big-endian representation
+    pub fn from_uncompressed_bytes(
+        bytes: &<S::SignatureGroup as Point>::UncompressedPairBytes,
+    ) -> CtOption<Self> {
+        let (u, v) = <S::SignatureGroup as Point>::split_uncompressed_pair(bytes);
+        <S::SignatureGroup as Point>::from_uncompressed(&u).and_then(|u| {
+            <S::SignatureGroup as Point>::from_uncompressed(&v).map(|v| Self { u, v })
+        })
+    }
+
+    /// Convert an uncompressed big-endian representation without
+    /// checking the points are on the curve or in the prime order subgroup.
+    ///
+    /// **Only use this with trusted input.** The result can be checked
+    /// later with `validate` or `validate_batch`.
+    pub fn from_uncompressed_bytes_unchecked(
+        bytes: &<S::SignatureGroup as Point>::UncompressedPairBytes,
+    ) -> CtOption<Self> {
+        let (u, v) = <S::SignatureGroup as Point>::split_uncompressed_pair(bytes);
+        <S::SignatureGroup as Point>::from_uncompressed_unchecked(&u).and_then(|u| {
+            <S::SignatureGroup as Point>::from_uncompressed_unchecked(&v).map(|v| Self { u, v })
+        })
+    }
+
+    /// Convert a big-endian representation and return the reason if it's invalid
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+        let bytes = crate::Error::check_bytes(bytes, Self::BYTES)?;
+        let proof = Option::<Self>::from(Self::from_bytes_unchecked(&bytes))
+            .ok_or(crate::Error::InvalidEncoding)?;
+        proof.validate()?;
+        Ok(proof)
+    }
+
+    /// Convert an uncompressed big-endian representation
+    /// and return the reason if it's invalid
+    pub fn try_from_uncompressed_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+        let bytes = crate::Error::check_bytes(bytes, Self::UNCOMPRESSED_BYTES)?;
+        let proof = Option::<Self>::from(Self::from_uncompressed_bytes_unchecked(&bytes))
+            .ok_or(crate::Error::InvalidEncoding)?;
+        proof.validate()?;
+        Ok(proof)
+    }
+}
+
 /// A signature proof of knowledge where the
 /// challenge is derived from a timestamp
 #[derive(Clone, Copy, Debug, Deserialize, Serialize)]
-pub struct ProofOfKnowledgeTimestamp {
+#[serde(bound(
+    serialize = "ProofOfKnowledgeOf<S>: Serialize",
+    deserialize = "ProofOfKnowledgeOf<S>: Deserialize<'de>"
+))]
+pub struct ProofOfKnowledgeTimestampOf<S: Scheme> {
     /// The signature proof of knowledge
-    pub pok: ProofOfKnowledge,
+    pub pok: ProofOfKnowledgeOf<S>,
     /// The timestamp for this proof
     pub t: i64,
 }
 
-#[cfg(feature = "iso8601-timestamp")]
-impl ProofOfKnowledgeTimestamp {
-    /// Verify the proof of knowledge
-    pub fn verify<B: AsRef<[u8]>>(&self, pk: PublicKey, msg: B, timeout_ms: i64) -> Choice {
-        let now = iso8601_timestamp::Timestamp::now_utc();
-        let since = iso8601_timestamp::Timestamp::UNIX_EPOCH
-            .saturating_add(iso8601_timestamp::Duration::milliseconds(self.t));
-        let elapsed = now.duration_since(since).whole_milliseconds() as i64;
-        if elapsed > timeout_ms {
+/// A signature proof of knowledge in G1 where the challenge is derived from a timestamp
+pub type ProofOfKnowledgeTimestamp = ProofOfKnowledgeTimestampOf<MinSig>;
+
+/// A signature proof of knowledge in G2 where the challenge is derived from a timestamp
+pub type ProofOfKnowledgeVtTimestamp = ProofOfKnowledgeTimestampOf<MinPk>;
+
+impl<S: Scheme> ProofOfKnowledgeTimestampOf<S> {
+    /// Verify the proof of knowledge against the system clock.
+    /// The timestamp must be no more than `timeout_ms` in the past
+    /// and not in the future, use [`Self::verify_with_clock`] to allow clock skew.
+    #[cfg(feature = "iso8601-timestamp")]
+    pub fn verify<B: AsRef<[u8]>>(&self, pk: PublicKeyOf<S>, msg: B, timeout_ms: i64) -> Choice {
+        self.verify_with_clock(pk, msg, timeout_ms, 0, &crate::SystemClock)
+    }
+
+    /// Verify the proof of knowledge against the time from `clock`.
+    /// The timestamp must be no more than `timeout_ms` in the past and
+    /// no more than `skew_ms` in the future. The past bound is also widened
+    /// by `skew_ms` so the tolerance for clock drift is symmetric.
+    pub fn verify_with_clock<B: AsRef<[u8]>, C: Clock>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+        timeout_ms: i64,
+        skew_ms: i64,
+        clock: &C,
+    ) -> Choice {
+        if timeout_ms < 0 || skew_ms < 0 {
+            return 0u8.into();
+        }
+        let elapsed = clock.now_ms().saturating_sub(self.t);
+        if elapsed > timeout_ms.saturating_add(skew_ms) || elapsed < -skew_ms {
             return 0u8.into();
         }
 
-        let y = ProofOfKnowledge::compute_y(self.pok.u, self.t);
+        let y = ProofOfKnowledgeOf::<S>::compute_y(self.pok.u, self.t);
         self.pok.verify(pk, msg, y)
     }
+
+    /// Verify the proof of knowledge against the system clock
+    /// and return the reason if it doesn't
+    #[cfg(feature = "iso8601-timestamp")]
+    pub fn try_verify<B: AsRef<[u8]>>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+        timeout_ms: i64,
+    ) -> Result<(), crate::Error> {
+        self.try_verify_with_clock(pk, msg, timeout_ms, 0, &crate::SystemClock)
+    }
+
+    /// Verify the proof of knowledge against the time from `clock`
+    /// and return the reason if it doesn't
+    pub fn try_verify_with_clock<B: AsRef<[u8]>, C: Clock>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+        timeout_ms: i64,
+        skew_ms: i64,
+        clock: &C,
+    ) -> Result<(), crate::Error> {
+        if timeout_ms < 0 || skew_ms < 0 {
+            return Err(crate::Error::InvalidTimestamp);
+        }
+        let elapsed = clock.now_ms().saturating_sub(self.t);
+        if elapsed > timeout_ms.saturating_add(skew_ms) || elapsed < -skew_ms {
+            return Err(crate::Error::InvalidTimestamp);
+        }
+
+        let y = ProofOfKnowledgeOf::<S>::compute_y(self.pok.u, self.t);
+        self.pok.try_verify(pk, msg, y)
+    }
 }
 
 #[test]
@@ -146,6 +478,25 @@ fn proof_works() {
     proof.u *= t;
     assert_eq!(proof.verify(pk, msg, y).unwrap_u8(), 0u8);
 
+    let opt_proof = sig.proof_of_knowledge_with_context(pk, msg, b"session 1", x);
+    assert!(opt_proof.is_some());
+    let proof = opt_proof.unwrap();
+    let ctx = b"session 1";
+    assert_eq!(proof.verify_with_context(pk, msg, ctx).unwrap_u8(), 1u8);
+    assert_eq!(
+        proof.verify_with_context(pk, msg, b"session 2").unwrap_u8(),
+        0u8
+    );
+    assert_eq!(
+        proof.verify_with_context(pk, b"different", ctx).unwrap_u8(),
+        0u8
+    );
+    let other_pk = PublicKey::from(&SecretKey::hash(b"other").unwrap());
+    assert_eq!(
+        proof.verify_with_context(other_pk, msg, ctx).unwrap_u8(),
+        0u8
+    );
+
     #[cfg(feature = "iso8601-timestamp")]
     {
         let opt_proof = sig.proof_of_knowledge_with_timestamp(msg, x);
@@ -155,11 +506,67 @@ fn proof_works() {
         proof.pok.u *= t;
         proof.pok.v *= t;
         assert_eq!(proof.verify(pk, msg, 2000).unwrap_u8(), 0u8);
+
+        // Timestamps in the future are rejected
+        let future = FixedClock(SystemClock.now_ms() + 60_000);
+        let proof = sig.proof_of_knowledge_with_clock(msg, x, &future).unwrap();
+        assert_eq!(proof.verify(pk, msg, 2000).unwrap_u8(), 0u8);
+    }
+
+    let opt_proof = sig.proof_of_knowledge_with_clock(msg, x, &FixedClock(10_000));
+    assert!(opt_proof.is_some());
+    let proof = opt_proof.unwrap();
+    assert_eq!(proof.t, 10_000);
+    let verify_at = |now: i64, skew_ms: i64| {
+        proof
+            .verify_with_clock(pk, msg, 1000, skew_ms, &FixedClock(now))
+            .unwrap_u8()
+    };
+    assert_eq!(verify_at(10_500, 0), 1u8);
+    assert_eq!(verify_at(11_000, 0), 1u8);
+    assert_eq!(verify_at(11_001, 0), 0u8);
+    assert_eq!(verify_at(11_001, 100), 1u8);
+    // Timestamps from the future are only accepted within the skew
+    assert_eq!(verify_at(9_999, 0), 0u8);
+    assert_eq!(verify_at(9_900, 100), 1u8);
+    assert_eq!(verify_at(9_899, 100), 0u8);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn batch_proof_works() {
+    use crate::*;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([19u8; 16]);
+    let mut proofs = alloc::vec::Vec::new();
+    for i in 0..4u8 {
+        let sk = SecretKey::random(&mut rng).unwrap();
+        let pk = PublicKey::from(&sk);
+        let msg = [i; 8];
+        let sig = Signature::new(&sk, msg).unwrap();
+        let y = Scalar::random(&mut rng);
+        let proof = sig
+            .proof_of_knowledge(msg, Scalar::random(&mut rng), y)
+            .unwrap();
+        proofs.push((proof, pk, msg, y));
     }
+    assert_eq!(
+        ProofOfKnowledge::batch_verify(&proofs, &mut rng).unwrap_u8(),
+        1u8
+    );
+
+    // One bad proof fails the whole batch
+    proofs[2].3 = Scalar::random(&mut rng);
+    assert_eq!(
+        ProofOfKnowledge::batch_verify(&proofs, &mut rng).unwrap_u8(),
+        0u8
+    );
 }
 
 #[test]
 fn proof_serialization() {
+    use bls12_381_plus::G1Projective;
     use rand_core::SeedableRng;
 
     let mut rng = crate::MockRng::from_seed([7u8; 16]);
@@ -176,4 +583,143 @@ fn proof_serialization() {
     assert!(res_de_proof.is_ok());
     let de_proof = res_de_proof.unwrap();
     assert_eq!(de_proof, proof);
+}
+
+#[test]
+fn proof_vt_works() {
+    use crate::*;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([3u8; 16]);
+    let sk = SecretKey::hash(b"proof_test").unwrap();
+    let pk = PublicKeyVt::from(&sk);
+    let msg = b"proof_test_msg";
+    let sig = SignatureVt::new(&sk, msg).unwrap();
+
+    let x = Scalar::random(&mut rng);
+    let y = Scalar::random(&mut rng);
+
+    let opt_proof = sig.proof_of_knowledge(msg, x, y);
+    assert!(opt_proof.is_some());
+    let mut proof = opt_proof.unwrap();
+    assert_eq!(proof.verify(pk, msg, y).unwrap_u8(), 1u8);
+
+    assert_eq!(proof.verify(pk, b"different message", y).unwrap_u8(), 0u8);
+    assert_eq!(proof.verify(pk, msg, x).unwrap_u8(), 0u8);
+    assert_eq!(proof.verify(pk, msg, sk.0).unwrap_u8(), 0u8);
+
+    // Can't be replayed in another context
+    let t = Scalar::random(&mut rng);
+    proof.v *= t;
+    proof.u *= t;
+    assert_eq!(proof.verify(pk, msg, y).unwrap_u8(), 0u8);
+
+    let opt_proof = sig.proof_of_knowledge_with_context(pk, msg, b"session 1", x);
+    assert!(opt_proof.is_some());
+    let proof = opt_proof.unwrap();
+    let ctx = b"session 1";
+    assert_eq!(proof.verify_with_context(pk, msg, ctx).unwrap_u8(), 1u8);
+    assert_eq!(
+        proof.verify_with_context(pk, msg, b"session 2").unwrap_u8(),
+        0u8
+    );
+    assert_eq!(
+        proof.verify_with_context(pk, b"different", ctx).unwrap_u8(),
+        0u8
+    );
+    let other_pk = PublicKeyVt::from(&SecretKey::hash(b"other").unwrap());
+    assert_eq!(
+        proof.verify_with_context(other_pk, msg, ctx).unwrap_u8(),
+        0u8
+    );
+
+    #[cfg(feature = "iso8601-timestamp")]
+    {
+        let opt_proof = sig.proof_of_knowledge_with_timestamp(msg, x);
+        assert!(opt_proof.is_some());
+        let mut proof = opt_proof.unwrap();
+        assert_eq!(proof.verify(pk, msg, 2000).unwrap_u8(), 1u8);
+        proof.pok.u *= t;
+        proof.pok.v *= t;
+        assert_eq!(proof.verify(pk, msg, 2000).unwrap_u8(), 0u8);
+
+        // Timestamps in the future are rejected
+        let future = FixedClock(SystemClock.now_ms() + 60_000);
+        let proof = sig.proof_of_knowledge_with_clock(msg, x, &future).unwrap();
+        assert_eq!(proof.verify(pk, msg, 2000).unwrap_u8(), 0u8);
+    }
+
+    let opt_proof = sig.proof_of_knowledge_with_clock(msg, x, &FixedClock(10_000));
+    assert!(opt_proof.is_some());
+    let proof = opt_proof.unwrap();
+    assert_eq!(proof.t, 10_000);
+    let verify_at = |now: i64, skew_ms: i64| {
+        proof
+            .verify_with_clock(pk, msg, 1000, skew_ms, &FixedClock(now))
+            .unwrap_u8()
+    };
+    assert_eq!(verify_at(10_500, 0), 1u8);
+    assert_eq!(verify_at(11_000, 0), 1u8);
+    assert_eq!(verify_at(11_001, 0), 0u8);
+    assert_eq!(verify_at(11_001, 100), 1u8);
+    // Timestamps from the future are only accepted within the skew
+    assert_eq!(verify_at(9_999, 0), 0u8);
+    assert_eq!(verify_at(9_900, 100), 1u8);
+    assert_eq!(verify_at(9_899, 100), 0u8);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn batch_proof_vt_works() {
+    use crate::*;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([19u8; 16]);
+    let mut proofs = alloc::vec::Vec::new();
+    for i in 0..4u8 {
+        let sk = SecretKey::random(&mut rng).unwrap();
+        let pk = PublicKeyVt::from(&sk);
+        let msg = [i; 8];
+        let sig = SignatureVt::new(&sk, msg).unwrap();
+        let y = Scalar::random(&mut rng);
+        let proof = sig
+            .proof_of_knowledge(msg, Scalar::random(&mut rng), y)
+            .unwrap();
+        proofs.push((proof, pk, msg, y));
+    }
+    assert_eq!(
+        ProofOfKnowledgeVt::batch_verify(&proofs, &mut rng).unwrap_u8(),
+        1u8
+    );
+
+    // One bad proof fails the whole batch
+    proofs[2].3 = Scalar::random(&mut rng);
+    assert_eq!(
+        ProofOfKnowledgeVt::batch_verify(&proofs, &mut rng).unwrap_u8(),
+        0u8
+    );
+}
+
+#[test]
+fn proof_vt_serialization() {
+    use bls12_381_plus::G2Projective;
+    use rand_core::SeedableRng;
+
+    let mut rng = crate::MockRng::from_seed([7u8; 16]);
+    let proof = ProofOfKnowledgeVt {
+        u: G2Projective::random(&mut rng),
+        v: G2Projective::random(&mut rng),
+    };
+
+    let proof_bytes = serde_bare::to_vec(&proof).unwrap();
+    let res_de_proof = serde_bare::from_slice::<ProofOfKnowledgeVt>(&proof_bytes);
+    assert!(res_de_proof.is_ok());
+    let de_proof = res_de_proof.unwrap();
+    assert_eq!(de_proof, proof);
+
+    let proof_str = serde_json::to_string(&proof).unwrap();
+    let res_de_proof = serde_json::from_str::<ProofOfKnowledgeVt>(&proof_str);
+    assert!(res_de_proof.is_ok());
+    let de_proof = res_de_proof.unwrap();
+    assert_eq!(de_proof, proof);
 }
\ No newline at end of file
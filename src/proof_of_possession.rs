@@ -1,61 +1,244 @@
-use crate::{PublicKey, SecretKey};
-use bls12_381_plus::{
-    multi_miller_loop, ExpandMsgXmd, G1Affine, G1Projective, G2Affine, G2Prepared,
-};
+use crate::{MinPk, MinSig, Point, PreparedPublicKeyOf, PublicKeyOf, Scheme, SecretKey};
+use bls12_381_plus::ExpandMsgXmd;
 use ff::Field;
-use group::{Curve, Group};
+use group::Group;
 use subtle::{Choice, CtOption};
 
+/// The weighted hash, public key and proof for one proof in a batch
+#[cfg(feature = "alloc")]
+type BatchEntry<S> = (
+    <S as Scheme>::SignatureInput,
+    <S as Scheme>::PublicKeyInput,
+    <S as Scheme>::SignatureGroup,
+);
+
 /// A proof of possession of the secret key
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub struct ProofOfPossession(pub G1Projective);
+pub struct ProofOfPossessionOf<S: Scheme>(pub S::SignatureGroup);
+
+/// A proof of possession in G1 for public keys in G2
+pub type ProofOfPossession = ProofOfPossessionOf<MinSig>;
+
+/// A proof of possession in G2 for public keys in G1
+pub type ProofOfPossessionVt = ProofOfPossessionOf<MinPk>;
 
-display_one_impl!(ProofOfPossession);
+display_one_impl!(ProofOfPossessionOf<S>);
 
-serde_impl!(ProofOfPossession, G1Projective);
+serde_impl!(ProofOfPossessionOf<S>, SignatureGroup);
 
-cond_select_impl!(ProofOfPossession, G1Projective);
+uncompressed_impl!(ProofOfPossessionOf<S>, SignatureGroup);
 
-impl ProofOfPossession {
+cond_select_impl!(ProofOfPossessionOf<S>);
+
+impl<S: Scheme> ProofOfPossessionOf<S> {
     /// Number of bytes needed to represent the proof
-    pub const BYTES: usize = 48;
+    pub const BYTES: usize = <S::SignatureGroup as Point>::BYTES;
     /// The domain separation tag
-    const DST: &'static [u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const DST: &'static [u8] = S::POP_DST;
 
     /// Create a new proof of possession
     pub fn new(sk: &SecretKey) -> Option<Self> {
         if sk.0.is_zero().unwrap_u8() == 1u8 {
             return None;
         }
-        let pk = PublicKey::from(sk);
-        let a = G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(&pk.to_bytes(), Self::DST);
-        Some(Self(a * sk.0))
+        let pk = PublicKeyOf::<S>::from(sk);
+        Some(Self(Self::hash_key(&pk) * sk.0))
+    }
+
+    /// Create a new proof of possession and return the reason if it fails
+    pub fn try_new(sk: &SecretKey) -> Result<Self, crate::Error> {
+        Self::new(sk).ok_or(crate::Error::ZeroKey)
     }
 
     validity_checks!();
 
-    bytes_impl!(G1Affine, G1Projective);
+    bytes_impl!(S::SignatureGroup);
+
+    fn hash_key(pk: &PublicKeyOf<S>) -> S::SignatureGroup {
+        S::SignatureGroup::hash_to_curve::<ExpandMsgXmd<sha2::Sha256>>(
+            pk.to_bytes().as_ref(),
+            Self::DST,
+        )
+    }
 
     /// Verify if the proof is over `pk`
-    pub fn verify(&self, pk: PublicKey) -> Choice {
+    pub fn verify(&self, pk: PublicKeyOf<S>) -> Choice {
         if (self.is_invalid() | pk.is_invalid()).unwrap_u8() == 1 {
             return Choice::from(0);
         }
-        let a = G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(&pk.to_bytes(), Self::DST);
-        let g2 = -G2Affine::generator();
+        self.verify_unchecked(pk)
+    }
 
-        multi_miller_loop(&[
-            (&a.to_affine(), &G2Prepared::from(pk.0.to_affine())),
-            (&self.0.to_affine(), &G2Prepared::from(g2)),
+    /// Verify without checking the points, which must already be validated
+    pub(crate) fn verify_unchecked(&self, pk: PublicKeyOf<S>) -> Choice {
+        let a = Self::hash_key(&pk);
+
+        S::pairing_check(&[
+            (&S::signature_input(&a), &S::public_key_input(&pk.0)),
+            (&S::signature_input(&self.0), &S::neg_generator()),
         ])
-        .final_exponentiation()
-        .is_identity()
+    }
+
+    /// Verify if the proof is over `pk` and return the reason if it isn't
+    pub fn try_verify(&self, pk: PublicKeyOf<S>) -> Result<(), crate::Error> {
+        pk.validate()?;
+        self.validate()?;
+        crate::Error::verified(self.verify(pk))
+    }
+
+    /// Verify if the proof is over a prepared public key
+    pub fn verify_prepared(&self, pk: &PreparedPublicKeyOf<S>) -> Choice {
+        if self.is_invalid().unwrap_u8() == 1 {
+            return Choice::from(0);
+        }
+        let a = Self::hash_key(&pk.key);
+
+        S::with_neg_generator(|neg_generator| {
+            S::pairing_check(&[
+                (&S::signature_input(&a), &pk.prepared),
+                (&S::signature_input(&self.0), neg_generator),
+            ])
+        })
+    }
+
+    /// Verify if the proof is over a prepared public key
+    /// and return the reason if it isn't
+    pub fn try_verify_prepared(&self, pk: &PreparedPublicKeyOf<S>) -> Result<(), crate::Error> {
+        self.validate()?;
+        crate::Error::verified(self.verify_prepared(pk))
+    }
+
+    /// Verify many proofs of possession with a single final exponentiation.
+    /// Each proof is weighted by a random scalar from `rng` so the batch
+    /// only verifies if every proof in it is valid.
+    #[cfg(feature = "alloc")]
+    pub fn batch_verify(
+        proofs: &[(Self, PublicKeyOf<S>)],
+        mut rng: impl rand_core::RngCore + rand_core::CryptoRng,
+    ) -> Choice {
+        use alloc::vec::Vec;
+
+        if proofs.is_empty() {
+            return Choice::from(0);
+        }
+        let mut inputs = Vec::with_capacity(proofs.len());
+        for (pop, pk) in proofs {
+            if (pop.is_invalid() | pk.is_invalid()).unwrap_u8() == 1 {
+                return Choice::from(0);
+            }
+            inputs.push((pop, pk, bls12_381_plus::Scalar::random(&mut rng)));
+        }
+        Self::batch_check(&Self::batch_entries(&inputs))
+    }
+
+    /// Find the indices of the proofs in `proofs` that fail to verify.
+    /// The whole batch is checked at once and on failure it is
+    /// repeatedly split in half to isolate the invalid proofs.
+    #[cfg(feature = "alloc")]
+    pub fn batch_find_invalid(
+        proofs: &[(Self, PublicKeyOf<S>)],
+        mut rng: impl rand_core::RngCore + rand_core::CryptoRng,
+    ) -> alloc::vec::Vec<usize> {
+        use alloc::vec::Vec;
+
+        let mut invalid = Vec::new();
+        let mut inputs = Vec::with_capacity(proofs.len());
+        let mut indices = Vec::with_capacity(proofs.len());
+        for (i, (pop, pk)) in proofs.iter().enumerate() {
+            if (pop.is_invalid() | pk.is_invalid()).unwrap_u8() == 1 {
+                invalid.push(i);
+            } else {
+                inputs.push((pop, pk, bls12_381_plus::Scalar::random(&mut rng)));
+                indices.push(i);
+            }
+        }
+        let entries = Self::batch_entries(&inputs);
+
+        let mut ranges = alloc::vec![(0, entries.len())];
+        while let Some((start, end)) = ranges.pop() {
+            if start == end || Self::batch_check(&entries[start..end]).unwrap_u8() == 1 {
+                continue;
+            }
+            if end - start == 1 {
+                invalid.push(indices[start]);
+                continue;
+            }
+            let mid = start + (end - start) / 2;
+            ranges.push((mid, end));
+            ranges.push((start, mid));
+        }
+        invalid.sort_unstable();
+        invalid
+    }
+
+    #[cfg(feature = "alloc")]
+    fn batch_entries(
+        inputs: &[(&Self, &PublicKeyOf<S>, bls12_381_plus::Scalar)],
+    ) -> alloc::vec::Vec<BatchEntry<S>> {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            inputs
+                .par_iter()
+                .map(|(pop, pk, r)| pop.batch_entry(pk, *r))
+                .collect()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            inputs
+                .iter()
+                .map(|(pop, pk, r)| pop.batch_entry(pk, *r))
+                .collect()
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn batch_entry(&self, pk: &PublicKeyOf<S>, r: bls12_381_plus::Scalar) -> BatchEntry<S> {
+        let a = Self::hash_key(pk);
+        (
+            S::signature_input(&(a * r)),
+            S::public_key_input(&pk.0),
+            self.0 * r,
+        )
+    }
+
+    #[cfg(feature = "alloc")]
+    fn batch_check(entries: &[BatchEntry<S>]) -> Choice {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            let pop = crate::parallel::sum(entries.par_iter().map(|(_, _, p)| *p));
+            let pop = S::signature_input(&pop);
+            (crate::parallel::multi_miller_loop::<S, _>(entries, |(a, pk, _)| (a, pk))
+                + S::multi_miller_loop(&[(&pop, &S::neg_generator())]))
+            .final_exponentiation()
+            .is_identity()
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            use alloc::vec::Vec;
+
+            let mut pop = S::SignatureGroup::identity();
+            for (_, _, p) in entries {
+                pop += p;
+            }
+            let pop = S::signature_input(&pop);
+            let g = S::neg_generator();
+            let mut t = entries
+                .iter()
+                .map(|(a, pk, _)| (a, pk))
+                .collect::<Vec<(&S::SignatureInput, &S::PublicKeyInput)>>();
+            t.push((&pop, &g));
+            S::pairing_check(t.as_slice())
+        }
     }
 }
 
 #[test]
 fn pop_works() {
-    use crate::MockRng;
+    use crate::{MockRng, ProofOfPossession, PublicKey};
     use rand_core::SeedableRng;
 
     let seed = [2u8; 16];
@@ -65,3 +248,83 @@ fn pop_works() {
     let pk = PublicKey::from(&sk);
     assert_eq!(pop.verify(pk).unwrap_u8(), 1);
 }
+
+#[cfg(feature = "alloc")]
+#[test]
+fn batch_pop_works() {
+    use crate::{MockRng, ProofOfPossession, PublicKey};
+    use alloc::vec::Vec;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([29u8; 16]);
+    let mut proofs = Vec::new();
+    for _ in 0..7 {
+        let sk = SecretKey::random(&mut rng).unwrap();
+        proofs.push((ProofOfPossession::new(&sk).unwrap(), PublicKey::from(&sk)));
+    }
+    assert_eq!(
+        ProofOfPossession::batch_verify(&proofs, &mut rng).unwrap_u8(),
+        1
+    );
+    assert!(ProofOfPossession::batch_find_invalid(&proofs, &mut rng).is_empty());
+
+    let pop = proofs[1].0;
+    proofs[1].0 = proofs[5].0;
+    proofs[5].0 = pop;
+    assert_eq!(
+        ProofOfPossession::batch_verify(&proofs, &mut rng).unwrap_u8(),
+        0
+    );
+    assert_eq!(
+        ProofOfPossession::batch_find_invalid(&proofs, &mut rng),
+        [1, 5]
+    );
+}
+
+#[test]
+fn pop_vt_works() {
+    use crate::{MockRng, ProofOfPossessionVt, PublicKeyVt};
+    use rand_core::SeedableRng;
+
+    let seed = [2u8; 16];
+    let mut rng = MockRng::from_seed(seed);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pop = ProofOfPossessionVt::new(&sk).unwrap();
+    let pk = PublicKeyVt::from(&sk);
+    assert_eq!(pop.verify(pk).unwrap_u8(), 1);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn batch_pop_vt_works() {
+    use crate::{MockRng, ProofOfPossessionVt, PublicKeyVt};
+    use alloc::vec::Vec;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([29u8; 16]);
+    let mut proofs = Vec::new();
+    for _ in 0..7 {
+        let sk = SecretKey::random(&mut rng).unwrap();
+        proofs.push((
+            ProofOfPossessionVt::new(&sk).unwrap(),
+            PublicKeyVt::from(&sk),
+        ));
+    }
+    assert_eq!(
+        ProofOfPossessionVt::batch_verify(&proofs, &mut rng).unwrap_u8(),
+        1
+    );
+    assert!(ProofOfPossessionVt::batch_find_invalid(&proofs, &mut rng).is_empty());
+
+    let pop = proofs[1].0;
+    proofs[1].0 = proofs[5].0;
+    proofs[5].0 = pop;
+    assert_eq!(
+        ProofOfPossessionVt::batch_verify(&proofs, &mut rng).unwrap_u8(),
+        0
+    );
+    assert_eq!(
+        ProofOfPossessionVt::batch_find_invalid(&proofs, &mut rng),
+        [1, 5]
+    );
+}
@@ -1,41 +1,46 @@
-use super::SecretKey;
-use bls12_381_plus::{G2Affine, G2Projective};
-use group::Curve;
+use crate::{MinPk, MinSig, Scheme, SecretKey};
+use group::Group;
 use subtle::{Choice, CtOption};
 
-/// A BLS public key
+/// A BLS public key in the public key group of the scheme
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub struct PublicKey(pub G2Projective);
+pub struct PublicKeyOf<S: Scheme>(pub S::PublicKeyGroup);
 
-display_one_impl!(PublicKey);
+/// A BLS public key in G2
+pub type PublicKey = PublicKeyOf<MinSig>;
 
-impl From<&SecretKey> for PublicKey {
+/// A BLS public key in G1
+pub type PublicKeyVt = PublicKeyOf<MinPk>;
+
+display_one_impl!(PublicKeyOf<S>);
+
+impl<S: Scheme> From<&SecretKey> for PublicKeyOf<S> {
     fn from(s: &SecretKey) -> Self {
-        Self(G2Projective::GENERATOR * s.0)
+        Self(S::PublicKeyGroup::generator() * s.0)
     }
 }
 
-impl From<PublicKey> for [u8; PublicKey::BYTES] {
-    fn from(pk: PublicKey) -> Self {
-        pk.to_bytes()
-    }
-}
+into_bytes_impl!(PublicKey);
 
-impl<'a> From<&'a PublicKey> for [u8; PublicKey::BYTES] {
-    fn from(pk: &'a PublicKey) -> [u8; PublicKey::BYTES] {
-        pk.to_bytes()
-    }
-}
+into_bytes_impl!(PublicKeyVt);
+
+serde_impl!(PublicKeyOf<S>, PublicKeyGroup);
 
-serde_impl!(PublicKey, G2Projective);
+uncompressed_impl!(PublicKeyOf<S>, PublicKeyGroup);
 
-cond_select_impl!(PublicKey, G2Projective);
+cond_select_impl!(PublicKeyOf<S>);
 
-impl PublicKey {
+impl<S: Scheme> PublicKeyOf<S> {
     /// Number of bytes needed to represent the public key
-    pub const BYTES: usize = 96;
+    pub const BYTES: usize = <S::PublicKeyGroup as crate::Point>::BYTES;
 
     validity_checks!();
 
-    bytes_impl!(G2Affine, G2Projective);
+    /// `KeyValidate` from the IETF BLS signature draft, check the key is
+    /// not the identity and is in the prime order subgroup
+    pub fn key_validate(&self) -> Result<(), crate::Error> {
+        self.validate()
+    }
+
+    bytes_impl!(S::PublicKeyGroup);
 }
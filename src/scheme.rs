@@ -0,0 +1,415 @@
+use bls12_381_plus::{
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective,
+    InitExpandMessage, MillerLoopResult, Scalar,
+};
+use core::fmt::{Debug, Display};
+use group::{Curve, Group};
+use subtle::{Choice, ConditionallySelectable, CtOption};
+use vsss_rs::{Error, Shamir, Share};
+
+/// A group that signatures or public keys are placed in
+pub trait Point: Group<Scalar = Scalar> + ConditionallySelectable + Default + Display {
+    /// The affine representation
+    type Affine: Copy + Debug + Send + Sync;
+    /// The compressed byte representation
+    type Bytes: Copy + Debug + AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
+    /// The uncompressed byte representation
+    type UncompressedBytes: Copy + Debug + AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
+    /// The compressed byte representation of two points
+    type PairBytes: Copy + Debug + AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
+    /// The uncompressed byte representation of two points
+    type UncompressedPairBytes: Copy + Debug + AsRef<[u8]> + for<'a> TryFrom<&'a [u8]>;
+    /// A secret share of a point, the identifier followed by the compressed point
+    type Share: Copy + Debug + Default + Send + Sync;
+
+    /// Number of bytes needed to represent the compressed point
+    const BYTES: usize;
+    /// The number of uniform bytes needed to hash to this group
+    const HASH_LEN_IN_BYTES: u16;
+
+    /// Get the affine representation
+    fn affine(&self) -> Self::Affine;
+
+    /// Get the compressed byte representation
+    fn to_compressed(&self) -> Self::Bytes;
+
+    /// Convert a compressed big-endian representation
+    fn from_compressed(bytes: &Self::Bytes) -> CtOption<Self>;
+
+    /// Convert a compressed big-endian representation without the prime order subgroup check
+    fn from_compressed_unchecked(bytes: &Self::Bytes) -> CtOption<Self>;
+
+    /// Get the uncompressed byte representation
+    fn to_uncompressed(&self) -> Self::UncompressedBytes;
+
+    /// Convert an uncompressed big-endian representation
+    fn from_uncompressed(bytes: &Self::UncompressedBytes) -> CtOption<Self>;
+
+    /// Convert an uncompressed big-endian representation without
+    /// checking the point is on the curve or in the prime order subgroup
+    fn from_uncompressed_unchecked(bytes: &Self::UncompressedBytes) -> CtOption<Self>;
+
+    /// Get the compressed byte representation of `a` followed by `b`
+    fn pair_to_compressed(a: &Self, b: &Self) -> Self::PairBytes;
+
+    /// Split the compressed byte representation of two points
+    fn split_pair(bytes: &Self::PairBytes) -> (Self::Bytes, Self::Bytes);
+
+    /// Get the uncompressed byte representation of `a` followed by `b`
+    fn pair_to_uncompressed(a: &Self, b: &Self) -> Self::UncompressedPairBytes;
+
+    /// Split the uncompressed byte representation of two points
+    fn split_uncompressed_pair(
+        bytes: &Self::UncompressedPairBytes,
+    ) -> (Self::UncompressedBytes, Self::UncompressedBytes);
+
+    /// Check the point is on the curve
+    fn is_on_curve(&self) -> Choice;
+
+    /// Check the point is in the prime order subgroup
+    fn is_torsion_free(&self) -> Choice;
+
+    /// Hash `msg` to the group with the domain separation tag `dst`
+    fn hash_to_curve<X: for<'a> InitExpandMessage<'a>>(msg: &[u8], dst: &[u8]) -> Self;
+
+    /// Create the secret share of `point` for `identifier`
+    fn to_share(identifier: u8, point: &Self) -> Self::Share;
+
+    /// The identifier of a secret share
+    fn share_identifier(share: &Self::Share) -> u8;
+
+    /// The point of a secret share
+    fn share_point(share: &Self::Share) -> CtOption<Self>;
+
+    /// The point of a secret share without the prime order subgroup check
+    fn share_point_unchecked(share: &Self::Share) -> CtOption<Self>;
+
+    /// Interpolate the first `T` shares at zero
+    fn combine_shares<const T: usize, const N: usize>(
+        shares: &[Self::Share],
+    ) -> Result<Self, Error>;
+}
+
+macro_rules! point_impl {
+    ($affine:ident, $projective:ident, $bytes:expr, $len_in_bytes:expr) => {
+        impl Point for $projective {
+            type Affine = $affine;
+            type Bytes = [u8; $bytes];
+            type UncompressedBytes = [u8; $bytes * 2];
+            type PairBytes = [u8; $bytes * 2];
+            type UncompressedPairBytes = [u8; $bytes * 4];
+            type Share = Share<{ $bytes + 1 }>;
+
+            const BYTES: usize = $bytes;
+            const HASH_LEN_IN_BYTES: u16 = $len_in_bytes;
+
+            fn affine(&self) -> $affine {
+                self.to_affine()
+            }
+
+            fn to_compressed(&self) -> Self::Bytes {
+                self.to_affine().to_compressed()
+            }
+
+            fn from_compressed(bytes: &Self::Bytes) -> CtOption<Self> {
+                $affine::from_compressed(bytes).map(|p| $projective::from(&p))
+            }
+
+            fn from_compressed_unchecked(bytes: &Self::Bytes) -> CtOption<Self> {
+                $affine::from_compressed_unchecked(bytes).map(|p| $projective::from(&p))
+            }
+
+            fn to_uncompressed(&self) -> Self::UncompressedBytes {
+                self.to_affine().to_uncompressed()
+            }
+
+            fn from_uncompressed(bytes: &Self::UncompressedBytes) -> CtOption<Self> {
+                $affine::from_uncompressed(bytes).map(|p| $projective::from(&p))
+            }
+
+            fn from_uncompressed_unchecked(bytes: &Self::UncompressedBytes) -> CtOption<Self> {
+                $affine::from_uncompressed_unchecked(bytes).map(|p| $projective::from(&p))
+            }
+
+            fn pair_to_compressed(a: &Self, b: &Self) -> Self::PairBytes {
+                let mut bytes = [0u8; $bytes * 2];
+                bytes[..$bytes].copy_from_slice(&Point::to_compressed(a));
+                bytes[$bytes..].copy_from_slice(&Point::to_compressed(b));
+                bytes
+            }
+
+            fn split_pair(bytes: &Self::PairBytes) -> (Self::Bytes, Self::Bytes) {
+                let mut a = [0u8; $bytes];
+                let mut b = [0u8; $bytes];
+                a.copy_from_slice(&bytes[..$bytes]);
+                b.copy_from_slice(&bytes[$bytes..]);
+                (a, b)
+            }
+
+            fn pair_to_uncompressed(a: &Self, b: &Self) -> Self::UncompressedPairBytes {
+                let mut bytes = [0u8; $bytes * 4];
+                bytes[..$bytes * 2].copy_from_slice(&Point::to_uncompressed(a));
+                bytes[$bytes * 2..].copy_from_slice(&Point::to_uncompressed(b));
+                bytes
+            }
+
+            fn split_uncompressed_pair(
+                bytes: &Self::UncompressedPairBytes,
+            ) -> (Self::UncompressedBytes, Self::UncompressedBytes) {
+                let mut a = [0u8; $bytes * 2];
+                let mut b = [0u8; $bytes * 2];
+                a.copy_from_slice(&bytes[..$bytes * 2]);
+                b.copy_from_slice(&bytes[$bytes * 2..]);
+                (a, b)
+            }
+
+            fn is_on_curve(&self) -> Choice {
+                $projective::is_on_curve(self)
+            }
+
+            fn is_torsion_free(&self) -> Choice {
+                self.to_affine().is_torsion_free()
+            }
+
+            fn hash_to_curve<X: for<'a> InitExpandMessage<'a>>(msg: &[u8], dst: &[u8]) -> Self {
+                $projective::hash::<X>(msg, dst)
+            }
+
+            fn to_share(identifier: u8, point: &Self) -> Self::Share {
+                let mut bytes = [0u8; $bytes + 1];
+                bytes[0] = identifier;
+                bytes[1..].copy_from_slice(&point.to_affine().to_compressed());
+                Share(bytes)
+            }
+
+            fn share_identifier(share: &Self::Share) -> u8 {
+                share.identifier()
+            }
+
+            fn share_point(share: &Self::Share) -> CtOption<Self> {
+                // shares are always one byte longer than the point so `unwrap` is okay
+                let bytes = <[u8; $bytes]>::try_from(share.value()).unwrap();
+                Self::from_compressed(&bytes)
+            }
+
+            fn share_point_unchecked(share: &Self::Share) -> CtOption<Self> {
+                let bytes = <[u8; $bytes]>::try_from(share.value()).unwrap();
+                Self::from_compressed_unchecked(&bytes)
+            }
+
+            fn combine_shares<const T: usize, const N: usize>(
+                shares: &[Self::Share],
+            ) -> Result<Self, Error> {
+                let mut pp = [Share::<{ $bytes + 1 }>::default(); T];
+                pp.copy_from_slice(&shares[..T]);
+                Shamir::<T, N>::combine_shares_group::<Scalar, $projective, { $bytes + 1 }>(&pp)
+            }
+        }
+    };
+}
+
+point_impl!(G1Affine, G1Projective, 48, 128);
+point_impl!(G2Affine, G2Projective, 96, 256);
+
+/// Where signatures and public keys are placed.
+///
+/// Every signature type is generic over the scheme so the same code
+/// backs both placements, e.g. [`crate::Signature`] is `SignatureOf<MinSig>`
+/// and [`crate::SignatureVt`] is `SignatureOf<MinPk>`.
+pub trait Scheme: Copy + Debug + Default + Eq + Send + Sync + 'static {
+    /// The group for signatures and hashed messages
+    type SignatureGroup: Point;
+    /// The group for public keys
+    type PublicKeyGroup: Point;
+    /// A point in the signature group ready for the miller loop
+    type SignatureInput: Clone + Debug + Send + Sync;
+    /// A point in the public key group ready for the miller loop
+    type PublicKeyInput: Clone + Debug + Send + Sync;
+
+    /// The signature domain separation tag
+    const SIGNATURE_DST: &'static [u8];
+    /// The proof of possession domain separation tag
+    const POP_DST: &'static [u8];
+    /// The domain separation tag for Fiat-Shamir proof of knowledge challenges
+    const POK_CONTEXT_DST: &'static [u8];
+    /// The domain separation tag for timestamp proof of knowledge challenges
+    const POK_TIMESTAMP_DST: &'static [u8];
+    /// The domain separation tag for accountable-subgroup multisignature messages
+    const ASM_MSG_DST: &'static [u8];
+    /// The domain separation tag for accountable-subgroup member indices
+    const ASM_INDEX_DST: &'static [u8];
+
+    /// Prepare a point in the signature group for the miller loop
+    fn signature_input(point: &Self::SignatureGroup) -> Self::SignatureInput;
+
+    /// Prepare a point in the public key group for the miller loop
+    fn public_key_input(point: &Self::PublicKeyGroup) -> Self::PublicKeyInput;
+
+    /// Compute the product of the miller loops of each pair
+    /// before the final exponentiation
+    fn multi_miller_loop(
+        terms: &[(&Self::SignatureInput, &Self::PublicKeyInput)],
+    ) -> MillerLoopResult;
+
+    /// Check the product of the pairings of each pair is the identity
+    fn pairing_check(terms: &[(&Self::SignatureInput, &Self::PublicKeyInput)]) -> Choice {
+        Self::multi_miller_loop(terms)
+            .final_exponentiation()
+            .is_identity()
+    }
+
+    /// The negated generator of the public key group ready for the miller loop
+    fn neg_generator() -> Self::PublicKeyInput {
+        Self::public_key_input(&-Self::PublicKeyGroup::generator())
+    }
+
+    /// Call `f` with the negated generator of the public key group ready for
+    /// the miller loop. With `std` it's computed once and shared by every caller.
+    fn with_neg_generator<R>(f: impl FnOnce(&Self::PublicKeyInput) -> R) -> R;
+}
+
+macro_rules! with_neg_generator_impl {
+    ($input:ident) => {
+        fn with_neg_generator<R>(f: impl FnOnce(&Self::PublicKeyInput) -> R) -> R {
+            #[cfg(feature = "std")]
+            {
+                static NEG_GENERATOR: std::sync::OnceLock<$input> = std::sync::OnceLock::new();
+                f(NEG_GENERATOR.get_or_init(Self::neg_generator))
+            }
+            #[cfg(not(feature = "std"))]
+            {
+                f(&Self::neg_generator())
+            }
+        }
+    };
+}
+
+/// Signatures in G1 and public keys in G2, used by the normal types
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MinSig;
+
+/// Signatures in G2 and public keys in G1, used by the `Vt` types
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MinPk;
+
+impl Scheme for MinSig {
+    type SignatureGroup = G1Projective;
+    type PublicKeyGroup = G2Projective;
+    type SignatureInput = G1Affine;
+    type PublicKeyInput = G2Prepared;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    const POK_CONTEXT_DST: &'static [u8] = b"BLS12381G1-SIG-PROOF-OF-KNOWLEDGE-FIAT-SHAMIR-";
+    const POK_TIMESTAMP_DST: &'static [u8] = b"BLS12381G1-SIG-PROOF-OF-KNOWLEDGE-TIMESTAMP-";
+    const ASM_MSG_DST: &'static [u8] = b"BLS_ASM_BLS12381G1_XMD:SHA-256_SSWU_RO_MSG_";
+    const ASM_INDEX_DST: &'static [u8] = b"BLS_ASM_BLS12381G1_XMD:SHA-256_SSWU_RO_IDX_";
+
+    fn signature_input(point: &G1Projective) -> G1Affine {
+        point.to_affine()
+    }
+
+    fn public_key_input(point: &G2Projective) -> G2Prepared {
+        G2Prepared::from(point.to_affine())
+    }
+
+    fn multi_miller_loop(terms: &[(&G1Affine, &G2Prepared)]) -> MillerLoopResult {
+        multi_miller_loop(terms)
+    }
+
+    with_neg_generator_impl!(G2Prepared);
+}
+
+impl Scheme for MinPk {
+    type SignatureGroup = G2Projective;
+    type PublicKeyGroup = G1Projective;
+    type SignatureInput = G2Prepared;
+    type PublicKeyInput = G1Affine;
+
+    const SIGNATURE_DST: &'static [u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const POP_DST: &'static [u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+    const POK_CONTEXT_DST: &'static [u8] = b"BLS12381G2-SIG-PROOF-OF-KNOWLEDGE-FIAT-SHAMIR-";
+    const POK_TIMESTAMP_DST: &'static [u8] = b"BLS12381G2-SIG-PROOF-OF-KNOWLEDGE-TIMESTAMP-";
+    const ASM_MSG_DST: &'static [u8] = b"BLS_ASM_BLS12381G2_XMD:SHA-256_SSWU_RO_MSG_";
+    const ASM_INDEX_DST: &'static [u8] = b"BLS_ASM_BLS12381G2_XMD:SHA-256_SSWU_RO_IDX_";
+
+    fn signature_input(point: &G2Projective) -> G2Prepared {
+        G2Prepared::from(point.to_affine())
+    }
+
+    fn public_key_input(point: &G1Projective) -> G1Affine {
+        point.to_affine()
+    }
+
+    fn multi_miller_loop(terms: &[(&G2Prepared, &G1Affine)]) -> MillerLoopResult {
+        // The pairing takes G1 first so the pairs are swapped
+        #[cfg(feature = "alloc")]
+        {
+            let t = terms
+                .iter()
+                .map(|(s, p)| (*p, *s))
+                .collect::<alloc::vec::Vec<(&G1Affine, &G2Prepared)>>();
+            multi_miller_loop(t.as_slice())
+        }
+        #[cfg(not(feature = "alloc"))]
+        {
+            terms
+                .iter()
+                .fold(MillerLoopResult::default(), |res, (s, p)| {
+                    res + multi_miller_loop(&[(*p, *s)])
+                })
+        }
+    }
+
+    with_neg_generator_impl!(G1Affine);
+}
+
+#[test]
+fn scheme_works() {
+    use crate::{MockRng, SecretKey};
+    use bls12_381_plus::ExpandMsgXmd;
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([101u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+
+    // e(a * sk, g) * e(a, -g * sk) is the identity with either placement
+    let a =
+        G1Projective::hash_to_curve::<ExpandMsgXmd<sha2::Sha256>>(b"scheme", MinSig::SIGNATURE_DST);
+    let sig = MinSig::signature_input(&(a * sk.0));
+    let a = MinSig::signature_input(&a);
+    let pk = MinSig::public_key_input(&(G2Projective::GENERATOR * sk.0));
+    let g = MinSig::public_key_input(&G2Projective::GENERATOR);
+    assert_eq!(
+        MinSig::pairing_check(&[(&a, &pk), (&sig, &MinSig::neg_generator())]).unwrap_u8(),
+        1u8
+    );
+    assert_eq!(
+        MinSig::pairing_check(&[(&a, &pk), (&sig, &g)]).unwrap_u8(),
+        0u8
+    );
+
+    let a =
+        G2Projective::hash_to_curve::<ExpandMsgXmd<sha2::Sha256>>(b"scheme", MinPk::SIGNATURE_DST);
+    let sig = MinPk::signature_input(&(a * sk.0));
+    let a = MinPk::signature_input(&a);
+    let pk = MinPk::public_key_input(&(G1Projective::GENERATOR * sk.0));
+    let g = MinPk::public_key_input(&G1Projective::GENERATOR);
+    assert_eq!(
+        MinPk::pairing_check(&[(&a, &pk), (&sig, &MinPk::neg_generator())]).unwrap_u8(),
+        1u8
+    );
+    assert_eq!(
+        MinPk::pairing_check(&[(&a, &pk), (&sig, &g)]).unwrap_u8(),
+        0u8
+    );
+
+    let point = G1Projective::GENERATOR * sk.0;
+    let share = G1Projective::to_share(3, &point);
+    assert_eq!(G1Projective::share_identifier(&share), 3);
+    assert_eq!(G1Projective::share_point(&share).unwrap(), point);
+    assert_eq!(
+        G1Projective::from_compressed(&point.to_compressed()).unwrap(),
+        point
+    );
+}
@@ -1,6 +1,7 @@
 use crate::{secret_key_share::SECRET_KEY_SHARE_BYTES, SecretKeyShare};
 use bls12_381_plus::Scalar;
 use core::mem::MaybeUninit;
+use ff::Field;
 use hkdf::HkdfExtract;
 use rand_core::{CryptoRng, RngCore};
 use subtle::CtOption;
@@ -26,7 +27,7 @@ impl<'a> From<&'a SecretKey> for [u8; SecretKey::BYTES] {
     }
 }
 
-serde_impl!(SecretKey, Scalar);
+serde_impl!(SecretKey);
 
 impl SecretKey {
     /// Number of bytes needed to represent the secret key
@@ -34,11 +35,21 @@ impl SecretKey {
 
     /// Compute a secret key from a hash
     pub fn hash<B: AsRef<[u8]>>(data: B) -> Option<Self> {
+        Self::try_hash(data).ok()
+    }
+
+    /// Compute a secret key from a hash and return the reason if it fails
+    pub fn try_hash<B: AsRef<[u8]>>(data: B) -> Result<Self, crate::Error> {
         generate_secret_key(data.as_ref())
     }
 
     /// Compute a secret key from a CS-PRNG
-    pub fn random(mut rng: impl RngCore + CryptoRng) -> Option<Self> {
+    pub fn random(rng: impl RngCore + CryptoRng) -> Option<Self> {
+        Self::try_random(rng).ok()
+    }
+
+    /// Compute a secret key from a CS-PRNG and return the reason if it fails
+    pub fn try_random(mut rng: impl RngCore + CryptoRng) -> Result<Self, crate::Error> {
         let mut data = [0u8; Self::BYTES];
         rng.fill_bytes(&mut data);
         generate_secret_key(&data)
@@ -60,6 +71,17 @@ impl SecretKey {
         Scalar::from_bytes(&t).map(SecretKey)
     }
 
+    /// Convert a big-endian representation of the secret key
+    /// and return the reason if it's invalid
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+        let sk = Option::<Self>::from(Self::from_bytes(crate::Error::check_length(bytes)?))
+            .ok_or(crate::Error::InvalidEncoding)?;
+        if sk.0.is_zero().unwrap_u8() == 1u8 {
+            return Err(crate::Error::ZeroKey);
+        }
+        Ok(sk)
+    }
+
     /// Secret share this key by creating `N` shares where `T` are required
     /// to combine back into this secret
     #[allow(unsafe_code)]
@@ -93,7 +115,7 @@ impl SecretKey {
     }
 }
 
-fn generate_secret_key(ikm: &[u8]) -> Option<SecretKey> {
+fn generate_secret_key(ikm: &[u8]) -> Result<SecretKey, crate::Error> {
     const SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
     const INFO: [u8; 2] = [0u8, 48u8];
 
@@ -103,9 +125,11 @@ fn generate_secret_key(ikm: &[u8]) -> Option<SecretKey> {
     let (_, h) = extractor.finalize();
 
     let mut output = [0u8; 48];
-    if h.expand(&INFO, &mut output).is_err() {
-        None
-    } else {
-        Some(SecretKey(Scalar::from_okm(&output)))
+    // 48 bytes is acceptable length so `unwrap` is okay
+    h.expand(&INFO, &mut output).unwrap();
+    let sk = SecretKey(Scalar::from_okm(&output));
+    if sk.0.is_zero().unwrap_u8() == 1u8 {
+        return Err(crate::Error::ZeroKey);
     }
+    Ok(sk)
 }
@@ -1,4 +1,6 @@
-use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use crate::{PublicKeyOf, Scheme};
+use bls12_381_plus::Scalar;
+use group::Group;
 use vsss_rs::Share;
 use zeroize::Zeroize;
 
@@ -42,24 +44,7 @@ impl<'a> From<&'a SecretKeyShare> for [u8; SecretKeyShare::BYTES] {
     }
 }
 
-impl Serialize for SecretKeyShare {
-    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
-    where
-        S: Serializer,
-    {
-        self.0.serialize(s)
-    }
-}
-
-impl<'de> Deserialize<'de> for SecretKeyShare {
-    fn deserialize<D>(d: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        let share = Share::<SECRET_KEY_SHARE_BYTES>::deserialize(d)?;
-        Ok(Self(share))
-    }
-}
+serde_impl!(SecretKeyShare);
 
 impl SecretKeyShare {
     /// Number of bytes needed to represent the secret key
@@ -74,14 +59,50 @@ impl SecretKeyShare {
         r == 0
     }
 
-    /// Get the byte representation of this key
+    /// Get the byte representation of this share.
+    /// The identifier followed by the big-endian scalar like [`crate::SecretKey::to_bytes`]
     pub fn to_bytes(&self) -> [u8; Self::BYTES] {
-        self.0 .0
+        let mut bytes = self.0 .0;
+        // Make big endian
+        bytes[1..].reverse();
+        bytes
     }
 
-    /// Convert a big-endian representation of the secret key.
+    /// Convert the identifier followed by the big-endian scalar
     pub fn from_bytes(bytes: &[u8; Self::BYTES]) -> Self {
-        Self(Share(*bytes))
+        let mut t = *bytes;
+        t[1..].reverse();
+        Self(Share(t))
+    }
+
+    /// Convert the identifier followed by the big-endian scalar
+    /// and return the reason if it's invalid
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, crate::Error> {
+        let sk = Self::from_bytes(crate::Error::check_length(bytes)?);
+        if sk.0.identifier() == 0 {
+            return Err(crate::Error::InvalidEncoding);
+        }
+        let mut value = [0u8; SECRET_KEY_SHARE_BYTES - 1];
+        value.copy_from_slice(sk.0.value());
+        if Scalar::from_bytes(&value).is_none().unwrap_u8() == 1u8 {
+            return Err(crate::Error::InvalidEncoding);
+        }
+        if sk.is_zero() {
+            return Err(crate::Error::ZeroKey);
+        }
+        Ok(sk)
+    }
+
+    /// The public key of this share in scheme `S` used to verify
+    /// the partial signatures it creates
+    pub fn public_key<S: Scheme>(&self) -> Option<PublicKeyOf<S>> {
+        if self.is_zero() {
+            return None;
+        }
+        let mut value = [0u8; SECRET_KEY_SHARE_BYTES - 1];
+        value.copy_from_slice(self.0.value());
+        Option::<Scalar>::from(Scalar::from_bytes(&value))
+            .map(|s| PublicKeyOf(S::PublicKeyGroup::generator() * s))
     }
 }
 
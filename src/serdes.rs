@@ -0,0 +1,340 @@
+use bls12_381_plus::{G1Affine, G1Projective, G2Affine, G2Projective};
+use core::fmt::{self, Formatter};
+use group::Curve;
+use serde::{
+    de::{self, SeqAccess, Visitor},
+    ser, Deserializer, Serializer,
+};
+
+/// The longest encoding of any type, a [`crate::ProofOfKnowledgeVt`] with uncompressed points
+const MAX_BYTES: usize = 384;
+
+/// A type with a fixed length canonical big-endian byte encoding.
+///
+/// This is the encoding used by serde and the [`base64`] adapter.
+pub trait ByteEncoding: Sized {
+    /// The byte representation
+    type Bytes: AsRef<[u8]>;
+
+    /// Get the canonical byte representation
+    fn encode(&self) -> Self::Bytes;
+
+    /// Convert the canonical byte representation
+    fn decode(bytes: &[u8]) -> Option<Self>;
+}
+
+/// A type whose points can also be encoded uncompressed.
+///
+/// This is the encoding used by the [`uncompressed`] adapter.
+pub trait UncompressedEncoding: Sized {
+    /// The uncompressed byte representation
+    type Bytes: AsRef<[u8]>;
+
+    /// Get the uncompressed byte representation
+    fn encode_uncompressed(&self) -> Self::Bytes;
+
+    /// Convert the uncompressed byte representation
+    fn decode_uncompressed(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! point_encoding_impl {
+    ($affine:ident, $projective:ident, $bytes:expr) => {
+        impl ByteEncoding for $projective {
+            type Bytes = [u8; $bytes];
+
+            fn encode(&self) -> Self::Bytes {
+                self.to_affine().to_compressed()
+            }
+
+            fn decode(bytes: &[u8]) -> Option<Self> {
+                let bytes = <&[u8; $bytes]>::try_from(bytes).ok()?;
+                Option::from($affine::from_compressed(bytes).map(|p| $projective::from(&p)))
+            }
+        }
+
+        impl UncompressedEncoding for $projective {
+            type Bytes = [u8; $bytes * 2];
+
+            fn encode_uncompressed(&self) -> Self::Bytes {
+                self.to_affine().to_uncompressed()
+            }
+
+            fn decode_uncompressed(bytes: &[u8]) -> Option<Self> {
+                let bytes = <&[u8; $bytes * 2]>::try_from(bytes).ok()?;
+                Option::from($affine::from_uncompressed(bytes).map(|p| $projective::from(&p)))
+            }
+        }
+    };
+}
+
+point_encoding_impl!(G1Affine, G1Projective, 48);
+point_encoding_impl!(G2Affine, G2Projective, 96);
+
+/// How the bytes are written to human-readable formats
+#[derive(Clone, Copy)]
+enum Text {
+    Hex,
+    Base64,
+}
+
+fn serialize_with<S: Serializer>(bytes: &[u8], text: Text, s: S) -> Result<S::Ok, S::Error> {
+    if !s.is_human_readable() {
+        return s.serialize_bytes(bytes);
+    }
+    let mut buffer = [0u8; MAX_BYTES * 2];
+    let out = match text {
+        Text::Hex => {
+            let out = &mut buffer[..bytes.len() * 2];
+            hex::encode_to_slice(bytes, out).map_err(ser::Error::custom)?;
+            // hex is always valid utf8 so `unwrap` is okay
+            core::str::from_utf8(out).unwrap()
+        }
+        Text::Base64 => {
+            use base64ct::{Base64, Encoding};
+            Base64::encode(bytes, &mut buffer).map_err(ser::Error::custom)?
+        }
+    };
+    s.serialize_str(out)
+}
+
+fn deserialize_with<'de, D, T>(
+    d: D,
+    text: Text,
+    decode: fn(&[u8]) -> Option<T>,
+) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    struct BytesVisitor<'a> {
+        buffer: &'a mut [u8; MAX_BYTES],
+        text: Text,
+    }
+
+    impl<'de, 'a> Visitor<'de> for BytesVisitor<'a> {
+        type Value = usize;
+
+        fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            match self.text {
+                Text::Hex => write!(f, "a hex string or bytes"),
+                Text::Base64 => write!(f, "a base64 string or bytes"),
+            }
+        }
+
+        fn visit_str<E: de::Error>(mut self, v: &str) -> Result<usize, E> {
+            match self.text {
+                Text::Hex => {
+                    if v.len() % 2 != 0 || v.len() > MAX_BYTES * 2 {
+                        return Err(E::invalid_length(v.len(), &self));
+                    }
+                    let len = v.len() / 2;
+                    hex::decode_to_slice(v, &mut self.buffer[..len])
+                        .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))?;
+                    Ok(len)
+                }
+                Text::Base64 => {
+                    use base64ct::{Base64, Encoding};
+                    Base64::decode(v, &mut self.buffer[..])
+                        .map(|b| b.len())
+                        .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+                }
+            }
+        }
+
+        fn visit_bytes<E: de::Error>(mut self, v: &[u8]) -> Result<usize, E> {
+            if v.len() > MAX_BYTES {
+                return Err(E::invalid_length(v.len(), &self));
+            }
+            self.buffer[..v.len()].copy_from_slice(v);
+            Ok(v.len())
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<usize, A::Error> {
+            let mut len = 0;
+            while let Some(b) = seq.next_element()? {
+                if len == MAX_BYTES {
+                    return Err(de::Error::invalid_length(len + 1, &self));
+                }
+                self.buffer[len] = b;
+                len += 1;
+            }
+            Ok(len)
+        }
+    }
+
+    let mut buffer = [0u8; MAX_BYTES];
+    let visitor = BytesVisitor {
+        buffer: &mut buffer,
+        text,
+    };
+    let len = if d.is_human_readable() {
+        d.deserialize_str(visitor)?
+    } else {
+        d.deserialize_bytes(visitor)?
+    };
+    decode(&buffer[..len]).ok_or_else(|| de::Error::custom("invalid encoding"))
+}
+
+/// Serialize the canonical bytes as hex for human-readable formats and raw bytes otherwise
+pub(crate) fn serialize<T: ByteEncoding, S: Serializer>(
+    value: &T,
+    s: S,
+) -> Result<S::Ok, S::Error> {
+    serialize_with(value.encode().as_ref(), Text::Hex, s)
+}
+
+/// Deserialize the canonical bytes as hex for human-readable formats and raw bytes otherwise
+pub(crate) fn deserialize<'de, T: ByteEncoding, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+    deserialize_with(d, Text::Hex, T::decode)
+}
+
+/// Deserialize the canonical bytes like [`deserialize`] but convert them with `decode`
+pub(crate) fn deserialize_by<'de, T, D: Deserializer<'de>>(
+    d: D,
+    decode: fn(&[u8]) -> Option<T>,
+) -> Result<T, D::Error> {
+    deserialize_with(d, Text::Hex, decode)
+}
+
+/// Serialize a sequence with each item as canonical bytes
+#[cfg(feature = "alloc")]
+pub(crate) mod canonical_vec {
+    use super::ByteEncoding;
+    use alloc::vec::Vec;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    struct Canonical<T>(T);
+
+    impl<T: ByteEncoding> Serialize for Canonical<&T> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            super::serialize(self.0, s)
+        }
+    }
+
+    impl<'de, T: ByteEncoding> Deserialize<'de> for Canonical<T> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            super::deserialize(d).map(Canonical)
+        }
+    }
+
+    pub(crate) fn serialize<T: ByteEncoding, S: Serializer>(
+        values: &[T],
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        s.collect_seq(values.iter().map(Canonical))
+    }
+
+    pub(crate) fn deserialize<'de, T: ByteEncoding, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<Vec<T>, D::Error> {
+        let values = Vec::<Canonical<T>>::deserialize(d)?;
+        Ok(values.into_iter().map(|v| v.0).collect())
+    }
+}
+
+/// Serialize the canonical bytes as base64 for human-readable formats and raw bytes otherwise.
+///
+/// Use with `#[serde(with = "signature_bls::base64")]`
+pub mod base64 {
+    use super::{ByteEncoding, Text};
+    use serde::{Deserializer, Serializer};
+
+    /// Serialize `value` as base64 or raw bytes
+    pub fn serialize<T: ByteEncoding, S: Serializer>(value: &T, s: S) -> Result<S::Ok, S::Error> {
+        super::serialize_with(value.encode().as_ref(), Text::Base64, s)
+    }
+
+    /// Deserialize `value` from base64 or raw bytes
+    pub fn deserialize<'de, T: ByteEncoding, D: Deserializer<'de>>(d: D) -> Result<T, D::Error> {
+        super::deserialize_with(d, Text::Base64, T::decode)
+    }
+}
+
+/// Serialize the uncompressed points as hex for human-readable formats and raw bytes otherwise.
+/// This trades size for faster deserialization.
+///
+/// Use with `#[serde(with = "signature_bls::uncompressed")]`
+pub mod uncompressed {
+    use super::{Text, UncompressedEncoding};
+    use serde::{Deserializer, Serializer};
+
+    /// Serialize `value` as uncompressed hex or raw bytes
+    pub fn serialize<T: UncompressedEncoding, S: Serializer>(
+        value: &T,
+        s: S,
+    ) -> Result<S::Ok, S::Error> {
+        super::serialize_with(value.encode_uncompressed().as_ref(), Text::Hex, s)
+    }
+
+    /// Deserialize `value` from uncompressed hex or raw bytes
+    pub fn deserialize<'de, T: UncompressedEncoding, D: Deserializer<'de>>(
+        d: D,
+    ) -> Result<T, D::Error> {
+        super::deserialize_with(d, Text::Hex, T::decode_uncompressed)
+    }
+}
+
+#[test]
+fn serdes_works() {
+    use crate::*;
+    use rand_core::SeedableRng;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+    struct Adapters {
+        #[serde(with = "base64")]
+        pk: PublicKey,
+        #[serde(with = "uncompressed")]
+        sig: Signature,
+    }
+
+    let mut rng = MockRng::from_seed([61u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let sig = Signature::new(&sk, b"serdes").unwrap();
+
+    // Secret keys use the same big-endian bytes as `to_bytes`
+    let sk_str = serde_json::to_string(&sk).unwrap();
+    let mut sk_hex = [0u8; SecretKey::BYTES];
+    hex::decode_to_slice(serde_json::from_str::<&str>(&sk_str).unwrap(), &mut sk_hex).unwrap();
+    assert_eq!(sk_hex, sk.to_bytes());
+    assert_eq!(serde_json::from_str::<SecretKey>(&sk_str).unwrap(), sk);
+    let sk_bytes = serde_bare::to_vec(&sk).unwrap();
+    assert_eq!(&sk_bytes[1..], &sk.to_bytes()[..]);
+    assert_eq!(serde_bare::from_slice::<SecretKey>(&sk_bytes).unwrap(), sk);
+
+    // Shares are the identifier followed by the big-endian scalar
+    let shares = sk.split::<MockRng, 2, 3>(&mut rng).unwrap();
+    let share_bytes = shares[0].to_bytes();
+    assert_eq!(share_bytes[0], shares[0].0.identifier());
+    let mut le = [0u8; SecretKey::BYTES];
+    le.copy_from_slice(&share_bytes[1..]);
+    le.reverse();
+    assert_eq!(&le[..], shares[0].0.value());
+    let share_str = serde_json::to_string(&shares[0]).unwrap();
+    let de_share = serde_json::from_str::<SecretKeyShare>(&share_str).unwrap();
+    assert_eq!(de_share.to_bytes(), share_bytes);
+    assert_eq!(
+        SecretKey::combine::<2, 3>(&[SecretKeyShare::from_bytes(&share_bytes), shares[1].clone()])
+            .unwrap(),
+        sk
+    );
+
+    let pk_str = serde_json::to_string(&pk).unwrap();
+    assert_eq!(pk_str.len(), PublicKey::BYTES * 2 + 2);
+    assert_eq!(serde_json::from_str::<PublicKey>(&pk_str).unwrap(), pk);
+    assert!(serde_json::from_str::<PublicKey>("\"00\"").is_err());
+    assert!(serde_json::from_str::<PublicKey>("\"zz\"").is_err());
+
+    let adapters = Adapters { pk, sig };
+    let json = serde_json::to_string(&adapters).unwrap();
+    assert_eq!(serde_json::from_str::<Adapters>(&json).unwrap(), adapters);
+    let bytes = serde_bare::to_vec(&adapters).unwrap();
+    assert_eq!(
+        bytes.len(),
+        1 + PublicKey::BYTES + 1 + Signature::UNCOMPRESSED_BYTES
+    );
+    assert_eq!(
+        serde_bare::from_slice::<Adapters>(&bytes).unwrap(),
+        adapters
+    );
+}
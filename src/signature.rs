@@ -1,78 +1,228 @@
-use crate::partial_signature::PARTIAL_SIGNATURE_BYTES;
-use crate::{PartialSignature, ProofOfKnowledge, PublicKey, SecretKey};
-use bls12_381_plus::{
-    multi_miller_loop, ExpandMsgXmd, G1Affine, G1Projective, G2Affine, G2Prepared, Scalar,
+use crate::{
+    Clock, HashedMessageOf, MinPk, MinSig, PartialSignatureOf, Point, PreparedPublicKeyOf,
+    ProofOfKnowledgeOf, ProofOfKnowledgeTimestampOf, PublicKeyOf, Scheme, SecretKey,
 };
+use bls12_381_plus::{ExpandMsgXmd, Scalar};
 use ff::Field;
-use group::{Curve, Group};
+use group::Group;
 use subtle::{Choice, CtOption};
-use vsss_rs::{Error, Shamir, Share};
+use vsss_rs::Error;
 
-/// Represents a BLS signature in G1 using the proof of possession scheme
+/// Represents a BLS signature in the signature group of the scheme
+/// using the proof of possession scheme
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
-pub struct Signature(pub G1Projective);
+pub struct SignatureOf<S: Scheme>(pub S::SignatureGroup);
+
+/// Represents a BLS signature in G1 using the proof of possession scheme
+pub type Signature = SignatureOf<MinSig>;
+
+/// Represents a BLS signature in G2 using the proof of possession scheme
+pub type SignatureVt = SignatureOf<MinPk>;
+
+display_one_impl!(SignatureOf<S>);
+
+serde_impl!(SignatureOf<S>, SignatureGroup);
+
+uncompressed_impl!(SignatureOf<S>, SignatureGroup);
 
-display_one_impl!(Signature);
+cond_select_impl!(SignatureOf<S>);
 
-serde_impl!(Signature, G1Projective);
+into_bytes_impl!(Signature);
 
-cond_select_impl!(Signature, G1Projective);
+into_bytes_impl!(SignatureVt);
+
+impl<S: Scheme> TryFrom<&[u8]> for SignatureOf<S> {
+    type Error = signature::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        Self::try_from_bytes(bytes).map_err(signature::Error::from)
+    }
+}
+
+impl signature::SignatureEncoding for Signature {
+    type Repr = [u8; Self::BYTES];
+}
 
-impl Signature {
+impl signature::SignatureEncoding for SignatureVt {
+    type Repr = [u8; Self::BYTES];
+}
+
+impl<S: Scheme> signature::Signer<SignatureOf<S>> for SecretKey {
+    fn try_sign(&self, msg: &[u8]) -> Result<SignatureOf<S>, signature::Error> {
+        SignatureOf::try_new(self, msg).map_err(signature::Error::from)
+    }
+}
+
+impl<S: Scheme> signature::Verifier<SignatureOf<S>> for PublicKeyOf<S> {
+    fn verify(&self, msg: &[u8], sig: &SignatureOf<S>) -> Result<(), signature::Error> {
+        sig.try_verify(*self, msg).map_err(signature::Error::from)
+    }
+}
+
+impl<S: Scheme> SignatureOf<S> {
     /// Number of bytes needed to represent the signature
-    pub const BYTES: usize = 48;
+    pub const BYTES: usize = <S::SignatureGroup as Point>::BYTES;
     /// The domain separation tag
-    const DST: &'static [u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+    pub(crate) const DST: &'static [u8] = S::SIGNATURE_DST;
 
     /// Create a new bls
     pub fn new<B: AsRef<[u8]>>(sk: &SecretKey, msg: B) -> Option<Self> {
-        if sk.0.is_zero().unwrap_u8() == 1u8 {
+        Self::new_prehashed(sk, &HashedMessageOf::new(msg))
+    }
+
+    /// Create a new bls over a message that has already been hashed
+    pub fn new_prehashed(sk: &SecretKey, msg: &HashedMessageOf<S>) -> Option<Self> {
+        if sk.0.is_zero().unwrap_u8() == 1u8 || !msg.is_signature_dst() {
             return None;
         }
-        let a = Self::hash_msg(msg.as_ref());
-        Some(Self(a * sk.0))
+        Some(Self(msg.point * sk.0))
     }
 
-    pub(crate) fn hash_msg(msg: &[u8]) -> G1Projective {
-        G1Projective::hash::<ExpandMsgXmd<sha2::Sha256>>(msg, Self::DST)
+    /// Create a new bls and return the reason if it fails
+    pub fn try_new<B: AsRef<[u8]>>(sk: &SecretKey, msg: B) -> Result<Self, crate::Error> {
+        Self::try_new_prehashed(sk, &HashedMessageOf::new(msg))
+    }
+
+    /// Create a new bls over a message that has already been hashed
+    /// and return the reason if it fails
+    pub fn try_new_prehashed(
+        sk: &SecretKey,
+        msg: &HashedMessageOf<S>,
+    ) -> Result<Self, crate::Error> {
+        if sk.0.is_zero().unwrap_u8() == 1u8 {
+            return Err(crate::Error::ZeroKey);
+        }
+        if !msg.is_signature_dst() {
+            return Err(crate::Error::InvalidDst);
+        }
+        Ok(Self(msg.point * sk.0))
+    }
+
+    pub(crate) fn hash_msg(msg: &[u8]) -> S::SignatureGroup {
+        S::SignatureGroup::hash_to_curve::<ExpandMsgXmd<sha2::Sha256>>(msg, Self::DST)
     }
 
     validity_checks!();
 
-    bytes_impl!(G1Affine, G1Projective);
+    bytes_impl!(S::SignatureGroup);
 
     /// Verify if the bls is over `msg` with `pk`
-    pub fn verify<B: AsRef<[u8]>>(&self, pk: PublicKey, msg: B) -> Choice {
-        if (pk.0.is_identity() | self.is_invalid()).unwrap_u8() == 1 {
+    pub fn verify<B: AsRef<[u8]>>(&self, pk: PublicKeyOf<S>, msg: B) -> Choice {
+        self.verify_prehashed(pk, &HashedMessageOf::new(msg))
+    }
+
+    /// Verify if the bls is over an already hashed `msg` with `pk`
+    pub fn verify_prehashed(&self, pk: PublicKeyOf<S>, msg: &HashedMessageOf<S>) -> Choice {
+        if (pk.is_invalid() | self.is_invalid()).unwrap_u8() == 1 {
             return 0u8.into();
         }
-        let a = Self::hash_msg(msg.as_ref());
-        let g2 = -G2Affine::generator();
+        self.verify_unchecked(pk, msg)
+    }
 
-        multi_miller_loop(&[
-            (&a.to_affine(), &G2Prepared::from(pk.0.to_affine())),
-            (&self.0.to_affine(), &G2Prepared::from(g2)),
+    /// Verify without checking the points, which must already be validated
+    pub(crate) fn verify_unchecked(&self, pk: PublicKeyOf<S>, msg: &HashedMessageOf<S>) -> Choice {
+        if !msg.is_signature_dst() {
+            return 0u8.into();
+        }
+        S::pairing_check(&[
+            (&S::signature_input(&msg.point), &S::public_key_input(&pk.0)),
+            (&S::signature_input(&self.0), &S::neg_generator()),
         ])
-        .final_exponentiation()
-        .is_identity()
+    }
+
+    /// Verify if the bls is over `msg` with `pk` and return the reason if it isn't
+    pub fn try_verify<B: AsRef<[u8]>>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+    ) -> Result<(), crate::Error> {
+        self.try_verify_prehashed(pk, &HashedMessageOf::new(msg))
+    }
+
+    /// Verify if the bls is over an already hashed `msg` with `pk`
+    /// and return the reason if it isn't
+    pub fn try_verify_prehashed(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: &HashedMessageOf<S>,
+    ) -> Result<(), crate::Error> {
+        pk.validate()?;
+        self.validate()?;
+        if !msg.is_signature_dst() {
+            return Err(crate::Error::InvalidDst);
+        }
+        crate::Error::verified(self.verify_prehashed(pk, msg))
+    }
+
+    /// Verify if the bls is over `msg` with a prepared public key
+    pub fn verify_prepared<B: AsRef<[u8]>>(&self, pk: &PreparedPublicKeyOf<S>, msg: B) -> Choice {
+        if self.is_invalid().unwrap_u8() == 1 {
+            return 0u8.into();
+        }
+        let a = Self::hash_msg(msg.as_ref());
+
+        S::with_neg_generator(|neg_generator| {
+            S::pairing_check(&[
+                (&S::signature_input(&a), &pk.prepared),
+                (&S::signature_input(&self.0), neg_generator),
+            ])
+        })
+    }
+
+    /// Verify if the bls is over `msg` with a prepared public key
+    /// and return the reason if it isn't
+    pub fn try_verify_prepared<B: AsRef<[u8]>>(
+        &self,
+        pk: &PreparedPublicKeyOf<S>,
+        msg: B,
+    ) -> Result<(), crate::Error> {
+        self.validate()?;
+        crate::Error::verified(self.verify_prepared(pk, msg))
     }
 
     /// Combine partial signatures into a completed signature
     pub fn from_partials<const T: usize, const N: usize>(
-        partials: &[PartialSignature],
+        partials: &[PartialSignatureOf<S>],
     ) -> Result<Self, Error> {
         if T > partials.len() {
             return Err(Error::SharingLimitLessThanThreshold);
         }
-        let mut pp = [Share::<PARTIAL_SIGNATURE_BYTES>::default(); T];
-        for i in 0..T {
-            pp[i] = partials[i].0;
+        if partials[..T]
+            .iter()
+            .any(|p| p.is_invalid().unwrap_u8() == 1u8)
+        {
+            return Err(Error::InvalidShare);
+        }
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            let points = partials[..T]
+                .par_iter()
+                .map(|p| {
+                    Option::<S::SignatureGroup>::from(S::SignatureGroup::share_point(&p.0))
+                        .map(|point| (S::SignatureGroup::share_identifier(&p.0), point))
+                })
+                .collect::<Option<alloc::vec::Vec<(u8, S::SignatureGroup)>>>()
+                .ok_or(Error::InvalidShare)?;
+            crate::parallel::combine_shares_group(&points).map(Self)
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            let mut pp = [<S::SignatureGroup as Point>::Share::default(); T];
+            for i in 0..T {
+                pp[i] = partials[i].0;
+            }
+            S::SignatureGroup::combine_shares::<T, N>(&pp).map(Self)
         }
-        let point =
-            Shamir::<T, N>::combine_shares_group::<Scalar, G1Projective, PARTIAL_SIGNATURE_BYTES>(
-                &pp,
-            )?;
-        Ok(Self(point))
+    }
+
+    /// Combine partial signatures into a completed signature
+    /// and return the reason if it fails
+    pub fn try_from_partials<const T: usize, const N: usize>(
+        partials: &[PartialSignatureOf<S>],
+    ) -> Result<Self, crate::Error> {
+        Ok(Self::from_partials::<T, N>(partials)?)
     }
 
     /// Create a zero-knowledge proof of a valid signature
@@ -83,29 +233,56 @@ impl Signature {
         msg: B,
         x: Scalar,
         y: Scalar,
-    ) -> Option<ProofOfKnowledge> {
-        if self.is_invalid().unwrap_u8() == 1u8 {
-            return None;
-        }
-        if x.is_zero().unwrap_u8() == 1u8 {
-            return None;
-        }
+    ) -> Option<ProofOfKnowledgeOf<S>> {
+        self.try_proof_of_knowledge(msg, x, y).ok()
+    }
+
+    /// Create a zero-knowledge proof of a valid signature
+    /// and return the reason if it fails
+    pub fn try_proof_of_knowledge<B: AsRef<[u8]>>(
+        &self,
+        msg: B,
+        x: Scalar,
+        y: Scalar,
+    ) -> Result<ProofOfKnowledgeOf<S>, crate::Error> {
+        self.validate()?;
         if y.is_zero().unwrap_u8() == 1u8 {
-            return None;
-        }
-        let a = Self::hash_msg(msg.as_ref());
-        if a.is_identity().unwrap_u8() == 1u8 {
-            return None;
-        }
-        let u = a * x;
-        if u.is_identity().unwrap_u8() == 1u8 {
-            return None;
-        }
-        let v = self.0 * (x + y);
-        if v.is_identity().unwrap_u8() == 1u8 {
-            return None;
+            return Err(crate::Error::ZeroScalar);
         }
-        Some(ProofOfKnowledge { u, v: -v })
+        let u = Self::pok_commitment(msg.as_ref(), x)?;
+        self.pok_response(u, x, y)
+    }
+
+    /// Create a non-interactive proof of knowledge where the challenge
+    /// is derived from `u`, `pk`, `msg` and `context` using Fiat-Shamir
+    /// `context` binds the proof to a session e.g. a nonce or the verifier's identity
+    /// `x` should be a random Scalar and kept private
+    pub fn proof_of_knowledge_with_context<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+        context: C,
+        x: Scalar,
+    ) -> Option<ProofOfKnowledgeOf<S>> {
+        self.try_proof_of_knowledge_with_context(pk, msg, context, x)
+            .ok()
+    }
+
+    /// Create a non-interactive proof of knowledge bound to `context`
+    /// and return the reason if it fails
+    pub fn try_proof_of_knowledge_with_context<B: AsRef<[u8]>, C: AsRef<[u8]>>(
+        &self,
+        pk: PublicKeyOf<S>,
+        msg: B,
+        context: C,
+        x: Scalar,
+    ) -> Result<ProofOfKnowledgeOf<S>, crate::Error> {
+        self.validate()?;
+        pk.validate()?;
+        let msg = msg.as_ref();
+        let u = Self::pok_commitment(msg, x)?;
+        let y = ProofOfKnowledgeOf::compute_context_y(u, pk, msg, context.as_ref());
+        self.pok_response(u, x, y)
     }
 
     /// Create a proof of knowledge based ona timestamp instead of a
@@ -116,40 +293,84 @@ impl Signature {
         &self,
         msg: B,
         x: Scalar,
-    ) -> Option<crate::ProofOfKnowledgeTimestamp> {
-        if self.is_invalid().unwrap_u8() == 1u8 {
-            return None;
-        }
+    ) -> Option<ProofOfKnowledgeTimestampOf<S>> {
+        self.proof_of_knowledge_with_clock(msg, x, &crate::SystemClock)
+    }
+
+    /// Create a proof of knowledge based on a timestamp
+    /// and return the reason if it fails
+    #[cfg(feature = "iso8601-timestamp")]
+    pub fn try_proof_of_knowledge_with_timestamp<B: AsRef<[u8]>>(
+        &self,
+        msg: B,
+        x: Scalar,
+    ) -> Result<ProofOfKnowledgeTimestampOf<S>, crate::Error> {
+        self.try_proof_of_knowledge_with_clock(msg, x, &crate::SystemClock)
+    }
+
+    /// Create a proof of knowledge based on a timestamp read from `clock`
+    /// `x` should be a random Scalar and kept private
+    pub fn proof_of_knowledge_with_clock<B: AsRef<[u8]>, C: Clock>(
+        &self,
+        msg: B,
+        x: Scalar,
+        clock: &C,
+    ) -> Option<ProofOfKnowledgeTimestampOf<S>> {
+        self.try_proof_of_knowledge_with_clock(msg, x, clock).ok()
+    }
+
+    /// Create a proof of knowledge based on a timestamp read from `clock`
+    /// and return the reason if it fails
+    pub fn try_proof_of_knowledge_with_clock<B: AsRef<[u8]>, C: Clock>(
+        &self,
+        msg: B,
+        x: Scalar,
+        clock: &C,
+    ) -> Result<ProofOfKnowledgeTimestampOf<S>, crate::Error> {
+        self.validate()?;
+        let u = Self::pok_commitment(msg.as_ref(), x)?;
+        let (y, t) = ProofOfKnowledgeOf::generate_timestamp_based_y(u, clock);
+        let pok = self.pok_response(u, x, y)?;
+        Ok(ProofOfKnowledgeTimestampOf { pok, t })
+    }
+
+    /// Commit to `msg` with the blinding factor `x`
+    fn pok_commitment(msg: &[u8], x: Scalar) -> Result<S::SignatureGroup, crate::Error> {
         if x.is_zero().unwrap_u8() == 1u8 {
-            return None;
+            return Err(crate::Error::ZeroScalar);
         }
-        let a = Self::hash_msg(msg.as_ref());
+        let a = Self::hash_msg(msg);
         if a.is_identity().unwrap_u8() == 1u8 {
-            return None;
+            return Err(crate::Error::IdentityPoint);
         }
         let u = a * x;
         if u.is_identity().unwrap_u8() == 1u8 {
-            return None;
+            return Err(crate::Error::IdentityPoint);
         }
-        let (y, t) = ProofOfKnowledge::generate_timestamp_based_y(u);
+        Ok(u)
+    }
+
+    /// Respond to the challenge `y` for the commitment `u`
+    fn pok_response(
+        &self,
+        u: S::SignatureGroup,
+        x: Scalar,
+        y: Scalar,
+    ) -> Result<ProofOfKnowledgeOf<S>, crate::Error> {
         if y.is_zero().unwrap_u8() == 1u8 {
-            return None;
+            return Err(crate::Error::ZeroScalar);
         }
-
         let v = self.0 * (x + y);
         if v.is_identity().unwrap_u8() == 1u8 {
-            return None;
+            return Err(crate::Error::IdentityPoint);
         }
-        Some(crate::ProofOfKnowledgeTimestamp {
-            pok: ProofOfKnowledge { u, v: -v },
-            t,
-        })
+        Ok(ProofOfKnowledgeOf { u, v: -v })
     }
 }
 
 #[test]
 fn signature_works() {
-    use crate::MockRng;
+    use crate::{MockRng, PublicKey};
     use rand_core::{RngCore, SeedableRng};
 
     let seed = [2u8; 16];
@@ -164,7 +385,7 @@ fn signature_works() {
 
 #[test]
 fn threshold_works() {
-    use crate::MockRng;
+    use crate::{MockRng, PartialSignature, PublicKey};
     use rand_core::{RngCore, SeedableRng};
 
     let seed = [3u8; 16];
@@ -198,3 +419,175 @@ fn threshold_works() {
         }
     }
 }
+
+#[test]
+fn uncompressed_works() {
+    use crate::{MockRng, ProofOfKnowledge, PublicKey};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([59u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let msg = b"uncompressed";
+    let sig = Signature::new(&sk, msg).unwrap();
+
+    let bytes = sig.to_uncompressed_bytes();
+    assert_eq!(bytes.len(), Signature::UNCOMPRESSED_BYTES);
+    assert_eq!(Signature::from_uncompressed_bytes(&bytes).unwrap(), sig);
+    assert_eq!(Signature::try_from_uncompressed_bytes(&bytes).unwrap(), sig);
+    assert_eq!(
+        Signature::from_uncompressed_bytes_unchecked(&bytes).unwrap(),
+        sig
+    );
+    assert_eq!(
+        Signature::from_bytes_unchecked(&sig.to_bytes()).unwrap(),
+        sig
+    );
+    let pk_bytes = pk.to_uncompressed_bytes();
+    assert_eq!(pk_bytes.len(), 192);
+    assert_eq!(PublicKey::from_uncompressed_bytes(&pk_bytes).unwrap(), pk);
+
+    let sigs = [sig, Signature::default(), sig];
+    assert!(Signature::validate_batch(&sigs[..1]).is_ok());
+    assert!(matches!(
+        Signature::validate_batch(&sigs),
+        Err((1, crate::Error::IdentityPoint))
+    ));
+
+    let y = Scalar::random(&mut rng);
+    let proof = sig
+        .proof_of_knowledge(msg, Scalar::random(&mut rng), y)
+        .unwrap();
+    let bytes = proof.to_bytes();
+    assert_eq!(ProofOfKnowledge::from_bytes(&bytes).unwrap(), proof);
+    let proof = ProofOfKnowledge::from_uncompressed_bytes_unchecked(&proof.to_uncompressed_bytes())
+        .unwrap();
+    assert!(ProofOfKnowledge::validate_batch(&[proof]).is_ok());
+    assert!(proof.try_verify(pk, msg, y).is_ok());
+}
+
+#[test]
+fn subgroup_checks_work() {
+    use crate::{MockRng, PartialSignature, ProofOfKnowledge, PublicKey};
+    use bls12_381_plus::{G1Affine, G1Projective};
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([89u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let msg = b"subgroup";
+    let sig = Signature::new(&sk, msg).unwrap();
+    assert_eq!(sig.is_valid().unwrap_u8(), 1u8);
+    assert!(pk.key_validate().is_ok());
+
+    // x = 1, y = 1 is not on the curve
+    let mut bytes = [0u8; Signature::UNCOMPRESSED_BYTES];
+    bytes[47] = 1;
+    bytes[95] = 1;
+    let off_curve = Signature::from_uncompressed_bytes_unchecked(&bytes).unwrap();
+    assert_eq!(off_curve.is_valid().unwrap_u8(), 0u8);
+    assert!(matches!(
+        off_curve.validate(),
+        Err(crate::Error::NotOnCurve)
+    ));
+    assert!(matches!(
+        Signature::try_from_uncompressed_bytes(&bytes),
+        Err(crate::Error::NotOnCurve)
+    ));
+
+    // Almost every point on the curve is outside the prime order subgroup
+    let mut bytes = [0u8; Signature::BYTES];
+    bytes[0] = 0x80;
+    let torsion = (1..=u8::MAX)
+        .find_map(|i| {
+            bytes[47] = i;
+            Option::<G1Affine>::from(G1Affine::from_compressed_unchecked(&bytes))
+                .filter(|p| p.is_torsion_free().unwrap_u8() == 0u8)
+        })
+        .unwrap();
+    let bad: Signature = SignatureOf(G1Projective::from(torsion));
+    assert_eq!(bad.is_invalid().unwrap_u8(), 1u8);
+    assert_eq!(bad.verify(pk, msg).unwrap_u8(), 0u8);
+    assert!(matches!(
+        bad.try_verify(pk, msg),
+        Err(crate::Error::NotInSubgroup)
+    ));
+    assert!(matches!(
+        Signature::try_from_bytes(&bad.to_bytes()),
+        Err(crate::Error::NotInSubgroup)
+    ));
+    assert!(Signature::try_from(&bad.to_bytes()[..]).is_err());
+    assert!(serde_json::from_str::<Signature>(&serde_json::to_string(&bad).unwrap()).is_err());
+    let proof = ProofOfKnowledge { u: bad.0, v: sig.0 };
+    assert_eq!(proof.is_invalid().unwrap_u8(), 1u8);
+    assert!(ProofOfKnowledge::try_from_bytes(&proof.to_bytes()).is_err());
+
+    let mut partial = [0u8; PartialSignature::BYTES];
+    partial[1..].copy_from_slice(&sig.to_bytes());
+    assert_eq!(
+        PartialSignature::from_bytes(&partial)
+            .is_invalid()
+            .unwrap_u8(),
+        1u8
+    );
+    assert!(PartialSignature::try_from_bytes(&partial).is_err());
+    partial[0] = 1;
+    assert!(PartialSignature::try_from_bytes(&partial).is_ok());
+    partial[1..].copy_from_slice(&bad.to_bytes());
+    assert!(matches!(
+        PartialSignature::try_from_bytes(&partial),
+        Err(crate::Error::NotInSubgroup)
+    ));
+}
+
+#[test]
+fn signature_vt_works() {
+    use crate::{MockRng, PublicKeyVt};
+    use rand_core::{RngCore, SeedableRng};
+
+    let seed = [2u8; 16];
+    let mut rng = MockRng::from_seed(seed);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let mut msg = [0u8; 12];
+    rng.fill_bytes(&mut msg);
+    let sig = SignatureVt::new(&sk, msg).unwrap();
+    let pk = PublicKeyVt::from(&sk);
+    assert_eq!(sig.verify(pk, msg).unwrap_u8(), 1);
+}
+
+#[test]
+fn threshold_vt_works() {
+    use crate::{MockRng, PartialSignatureVt, PublicKeyVt};
+    use rand_core::{RngCore, SeedableRng};
+
+    let seed = [3u8; 16];
+    let mut rng = MockRng::from_seed(seed);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKeyVt::from(&sk);
+
+    let res_shares = sk.split::<MockRng, 2, 3>(&mut rng);
+    assert!(res_shares.is_ok());
+    let shares = res_shares.unwrap();
+    let mut msg = [0u8; 12];
+    rng.fill_bytes(&mut msg);
+
+    let mut sigs = [PartialSignatureVt::default(); 3];
+    for (i, share) in shares.iter().enumerate() {
+        let opt = PartialSignatureVt::new(share, &msg);
+        assert!(opt.is_some());
+        sigs[i] = opt.unwrap();
+    }
+
+    // Try all combinations to make sure they verify
+    for i in 0..3 {
+        for j in 0..3 {
+            if i == j {
+                continue;
+            }
+            let res = SignatureVt::from_partials::<2, 3>(&[sigs[i], sigs[j]]);
+            assert!(res.is_ok());
+            let sig = res.unwrap();
+            assert_eq!(sig.verify(pk, msg).unwrap_u8(), 1);
+        }
+    }
+}
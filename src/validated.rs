@@ -0,0 +1,203 @@
+use crate::{
+    ByteEncoding, Error, HashedMessageOf, MultiPublicKeyOf, ProofOfPossessionOf, PublicKeyOf,
+    Scheme, SignatureOf,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use subtle::Choice;
+
+/// A key, signature or proof that can be checked with `validate`
+pub trait Validate: ByteEncoding + Copy {
+    /// Check the point is not the identity, is on the curve
+    /// and is in the prime order subgroup
+    fn validate(&self) -> Result<(), Error>;
+
+    /// Convert the canonical bytes without the prime order subgroup check
+    fn decode_unchecked(bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! validate_impl {
+    ($name:ident) => {
+        impl<S: Scheme> Validate for $name<S> {
+            fn validate(&self) -> Result<(), Error> {
+                $name::validate(self)
+            }
+
+            fn decode_unchecked(bytes: &[u8]) -> Option<Self> {
+                let bytes = Error::check_bytes(bytes, Self::BYTES).ok()?;
+                Option::<Self>::from(Self::from_bytes_unchecked(&bytes))
+            }
+        }
+    };
+}
+
+validate_impl!(PublicKeyOf);
+validate_impl!(SignatureOf);
+validate_impl!(ProofOfPossessionOf);
+validate_impl!(MultiPublicKeyOf);
+
+/// A value that has not been checked, e.g. one that was just deserialized.
+/// It must be converted to a [`Validated`] value before it can be
+/// aggregated or verified with the fast verify methods.
+///
+/// Deserializing skips the prime order subgroup check so a batch
+/// can be received first and checked later.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Unvalidated<T>(T);
+
+impl<T: Validate> From<T> for Unvalidated<T> {
+    fn from(value: T) -> Self {
+        Self(value)
+    }
+}
+
+impl<T: Validate> Unvalidated<T> {
+    /// Convert the canonical bytes without the prime order subgroup check
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        T::decode_unchecked(bytes)
+            .map(Self)
+            .ok_or(Error::InvalidEncoding)
+    }
+
+    /// Check the value and return the reason if it's invalid
+    pub fn validate(self) -> Result<Validated<T>, Error> {
+        self.0.validate()?;
+        Ok(Validated(self.0))
+    }
+
+    /// Get the value without checking it.
+    ///
+    /// **Only use this with trusted input.**
+    pub fn into_unchecked(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Validate> Serialize for Unvalidated<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        crate::serdes::serialize(&self.0, s)
+    }
+}
+
+impl<'de, T: Validate> Deserialize<'de> for Unvalidated<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        crate::serdes::deserialize_by(d, T::decode_unchecked).map(Self)
+    }
+}
+
+/// A value that has been checked with `validate`.
+/// Values can only be created by validating so only
+/// checked values can be passed to the methods that take them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Validated<T>(T);
+
+impl<T: Validate> Validated<T> {
+    /// Check `value` and return the reason if it's invalid
+    pub fn new(value: T) -> Result<Self, Error> {
+        Unvalidated(value).validate()
+    }
+
+    /// Get the checked value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> AsRef<T> for Validated<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Validate> Serialize for Validated<T> {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        crate::serdes::serialize(&self.0, s)
+    }
+}
+
+impl<'de, T: Validate> Deserialize<'de> for Validated<T> {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        Unvalidated::<T>::deserialize(d)?
+            .validate()
+            .map_err(de::Error::custom)
+    }
+}
+
+impl<S: Scheme> Validated<SignatureOf<S>> {
+    /// Verify the signature is over `msg` with `pk`
+    /// without repeating the point checks
+    pub fn verify<B: AsRef<[u8]>>(&self, pk: &Validated<PublicKeyOf<S>>, msg: B) -> Choice {
+        self.verify_prehashed(pk, &HashedMessageOf::new(msg))
+    }
+
+    /// Verify the signature is over an already hashed `msg` with `pk`
+    /// without repeating the point checks
+    pub fn verify_prehashed(
+        &self,
+        pk: &Validated<PublicKeyOf<S>>,
+        msg: &HashedMessageOf<S>,
+    ) -> Choice {
+        self.0.verify_unchecked(pk.0, msg)
+    }
+}
+
+impl<S: Scheme> Validated<ProofOfPossessionOf<S>> {
+    /// Verify the proof is over `pk` without repeating the point checks
+    pub fn verify(&self, pk: &Validated<PublicKeyOf<S>>) -> Choice {
+        self.0.verify_unchecked(pk.0)
+    }
+}
+
+#[test]
+fn validated_works() {
+    use crate::{
+        MockRng, MultiPublicKey, MultiSignature, PreparedPublicKey, ProofOfPossession, PublicKey,
+        SecretKey, Signature, SignatureVt,
+    };
+    use rand_core::SeedableRng;
+
+    let mut rng = MockRng::from_seed([97u8; 16]);
+    let sk1 = SecretKey::random(&mut rng).unwrap();
+    let sk2 = SecretKey::random(&mut rng).unwrap();
+    let msg = b"validated";
+
+    let pk1 = Validated::new(PublicKey::from(&sk1)).unwrap();
+    let pk2 = Validated::new(PublicKey::from(&sk2)).unwrap();
+    assert!(matches!(
+        Validated::new(PublicKey::default()),
+        Err(Error::IdentityPoint)
+    ));
+
+    let sig1 = Signature::new(&sk1, msg).unwrap();
+    let json = serde_json::to_string(&sig1).unwrap();
+    let unchecked = serde_json::from_str::<Unvalidated<Signature>>(&json).unwrap();
+    let sig1 = unchecked.validate().unwrap();
+    assert_eq!(sig1.verify(&pk1, msg).unwrap_u8(), 1u8);
+    assert_eq!(sig1.verify(&pk2, msg).unwrap_u8(), 0u8);
+    let bytes = serde_bare::to_vec(&sig1).unwrap();
+    assert_eq!(
+        serde_bare::from_slice::<Validated<Signature>>(&bytes).unwrap(),
+        sig1
+    );
+
+    let sig2 = Unvalidated::<Signature>::from_bytes(&Signature::new(&sk2, msg).unwrap().to_bytes())
+        .unwrap()
+        .validate()
+        .unwrap();
+    let mpk = MultiPublicKey::from(&[pk1, pk2][..]);
+    let msig = MultiSignature::from(&[sig1, sig2][..]);
+    assert_eq!(mpk.0, pk1.into_inner().0 + pk2.into_inner().0);
+    assert_eq!(msig.verify(mpk, msg).unwrap_u8(), 1u8);
+    let prepared = PreparedPublicKey::from(Validated::new(mpk).unwrap());
+    assert_eq!(msig.verify_prepared(&prepared, msg).unwrap_u8(), 1u8);
+
+    let pop = Validated::new(ProofOfPossession::new(&sk1).unwrap()).unwrap();
+    assert_eq!(pop.verify(&pk1).unwrap_u8(), 1u8);
+    assert_eq!(pop.verify(&pk2).unwrap_u8(), 0u8);
+
+    // The identity decodes without validation but can't be validated
+    let identity =
+        Unvalidated::<SignatureVt>::from_bytes(&SignatureVt::default().to_bytes()).unwrap();
+    assert!(matches!(identity.validate(), Err(Error::IdentityPoint)));
+    let json = serde_json::to_string(&identity).unwrap();
+    assert!(serde_json::from_str::<Validated<SignatureVt>>(&json).is_err());
+}
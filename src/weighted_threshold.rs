@@ -0,0 +1,481 @@
+use crate::{
+    Error, HashedMessageOf, MinPk, MinSig, PartialSignatureOf, Point, PublicKeyOf, Scheme,
+    SecretKey, SecretKeyShare, SignatureOf,
+};
+use alloc::vec::Vec;
+#[cfg(not(feature = "parallel"))]
+use bls12_381_plus::Scalar;
+use core::ops::Range;
+#[cfg(not(feature = "parallel"))]
+use ff::Field;
+#[cfg(not(feature = "parallel"))]
+use group::Group;
+use rand_core::{CryptoRng, RngCore};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Maps the weights of parties to the number of threshold shares each one holds.
+///
+/// Shares are allocated in proportion to weight using the largest remainder method
+/// so a set of parties holding a fraction of the total weight holds approximately
+/// the same fraction of the shares. Party `i` holds the consecutive shares given by
+/// [`ShareAllocation::share_range`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct ShareAllocation {
+    weights: Vec<u64>,
+    shares: Vec<usize>,
+}
+
+impl ShareAllocation {
+    /// Allocate `total_shares` among parties with `weights`.
+    /// `total_shares` cannot exceed 255 since share identifiers are a single byte.
+    pub fn new(weights: &[u64], total_shares: usize) -> Option<Self> {
+        let total_weight = Self::sum_weights(weights)?;
+        if weights.is_empty() || total_weight == 0 || total_shares == 0 || total_shares > 255 {
+            return None;
+        }
+        let total_weight = total_weight as u128;
+        let mut shares = Vec::with_capacity(weights.len());
+        let mut remainders = Vec::with_capacity(weights.len());
+        let mut allocated = 0usize;
+        for (i, w) in weights.iter().enumerate() {
+            let quota = (*w as u128) * (total_shares as u128);
+            let s = (quota / total_weight) as usize;
+            allocated += s;
+            shares.push(s);
+            remainders.push((quota % total_weight, i));
+        }
+        // Hand out what's left to the largest remainders, ties go to the lowest index
+        remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        for (_, i) in remainders.iter().take(total_shares - allocated) {
+            shares[*i] += 1;
+        }
+        Some(Self {
+            weights: weights.to_vec(),
+            shares,
+        })
+    }
+
+    /// The number of parties
+    pub fn parties(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// The sum of all party weights, `None` if it overflows
+    pub fn total_weight(&self) -> Option<u64> {
+        Self::sum_weights(&self.weights)
+    }
+
+    /// The total number of shares allocated
+    pub fn total_shares(&self) -> usize {
+        self.shares.iter().sum()
+    }
+
+    /// The weight of `party`
+    pub fn weight(&self, party: usize) -> u64 {
+        self.weights.get(party).copied().unwrap_or_default()
+    }
+
+    /// The number of shares held by `party`
+    pub fn shares(&self, party: usize) -> usize {
+        self.shares.get(party).copied().unwrap_or_default()
+    }
+
+    /// The positions of the shares held by `party` in the output of [`SecretKey::split`]
+    pub fn share_range(&self, party: usize) -> Option<Range<usize>> {
+        if party >= self.shares.len() {
+            return None;
+        }
+        let start = self.shares[..party].iter().sum();
+        Some(start..start + self.shares[party])
+    }
+
+    /// The number of shares needed so that at least `numerator / denominator`
+    /// of the total shares must be combined, i.e. the `T` to use with [`SecretKey::split`]
+    pub fn threshold(&self, numerator: u64, denominator: u64) -> usize {
+        if denominator == 0 {
+            return self.total_shares();
+        }
+        let n = (self.total_shares() as u128) * (numerator as u128);
+        n.div_ceil(denominator as u128) as usize
+    }
+
+    /// Secret share `sk` and hand out the shares according to this allocation.
+    /// `N` must equal [`ShareAllocation::total_shares`].
+    pub fn split<R: RngCore + CryptoRng, const T: usize, const N: usize>(
+        &self,
+        sk: &SecretKey,
+        rng: &mut R,
+    ) -> Result<Vec<WeightedKeyShares>, Error> {
+        if N != self.total_shares() {
+            return Err(Error::Sharing(vsss_rs::Error::InvalidShare));
+        }
+        let shares = sk.split::<R, T, N>(rng)?;
+        let mut output = Vec::with_capacity(self.parties());
+        for party in 0..self.parties() {
+            // Party is always in range so `unwrap` is okay
+            let range = self.share_range(party).unwrap();
+            output.push(WeightedKeyShares {
+                party,
+                weight: self.weights[party],
+                shares: shares[range].to_vec(),
+            });
+        }
+        Ok(output)
+    }
+
+    fn sum_weights(weights: &[u64]) -> Option<u64> {
+        weights.iter().try_fold(0u64, |acc, w| acc.checked_add(*w))
+    }
+}
+
+impl<'de> Deserialize<'de> for ShareAllocation {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename = "ShareAllocation")]
+        struct Raw {
+            weights: Vec<u64>,
+            shares: Vec<usize>,
+        }
+
+        let raw = Raw::deserialize(d)?;
+        let total_shares = raw
+            .shares
+            .iter()
+            .try_fold(0usize, |acc, s| acc.checked_add(*s))
+            .ok_or_else(|| serde::de::Error::custom("share count overflow"))?;
+        let allocation = Self::new(&raw.weights, total_shares)
+            .ok_or_else(|| serde::de::Error::custom("invalid share allocation"))?;
+        if allocation.shares != raw.shares {
+            return Err(serde::de::Error::custom("share allocation mismatch"));
+        }
+        Ok(allocation)
+    }
+}
+
+/// The secret key shares held by a single weighted party
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct WeightedKeyShares {
+    /// The index of the party in the [`ShareAllocation`]
+    pub party: usize,
+    /// The weight of the party
+    pub weight: u64,
+    /// The secret key shares held by the party
+    pub shares: Vec<SecretKeyShare>,
+}
+
+impl WeightedKeyShares {
+    /// Create a partial signature with every share held by this party
+    pub fn sign<S: Scheme, B: AsRef<[u8]>>(&self, msg: B) -> Option<WeightedPartialSignatureOf<S>> {
+        let msg = msg.as_ref();
+        let mut partials = Vec::with_capacity(self.shares.len());
+        for share in &self.shares {
+            partials.push(PartialSignatureOf::<S>::new(share, msg)?);
+        }
+        Some(WeightedPartialSignatureOf {
+            party: self.party,
+            partials,
+        })
+    }
+
+    /// The public keys of the shares held by this party used to
+    /// verify their partial signatures
+    pub fn public_keys<S: Scheme>(&self) -> Option<Vec<PublicKeyOf<S>>> {
+        self.shares.iter().map(|s| s.public_key()).collect()
+    }
+}
+
+/// The partial signatures produced by a single weighted party
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+#[serde(bound(
+    serialize = "PartialSignatureOf<S>: Serialize",
+    deserialize = "PartialSignatureOf<S>: Deserialize<'de>"
+))]
+pub struct WeightedPartialSignatureOf<S: Scheme> {
+    /// The index of the party in the [`ShareAllocation`]
+    pub party: usize,
+    /// One partial signature per share held by the party
+    pub partials: Vec<PartialSignatureOf<S>>,
+}
+
+/// The partial signatures in G1 produced by a single weighted party
+pub type WeightedPartialSignature = WeightedPartialSignatureOf<MinSig>;
+
+/// The partial signatures in G2 produced by a single weighted party
+pub type WeightedPartialSignatureVt = WeightedPartialSignatureOf<MinPk>;
+
+/// Collects weighted partial signatures until enough weight
+/// has signed to produce a completed signature.
+///
+/// Every partial signature is checked against the public key of the
+/// share that created it when added, so an invalid partial is attributed
+/// to the party that sent it instead of corrupting the completed signature.
+#[derive(Clone, Debug)]
+pub struct WeightedCombinerOf<'a, S: Scheme> {
+    allocation: &'a ShareAllocation,
+    share_keys: &'a [PublicKeyOf<S>],
+    msg: HashedMessageOf<S>,
+    numerator: u64,
+    denominator: u64,
+    signed: Vec<bool>,
+    weight: u64,
+    partials: Vec<PartialSignatureOf<S>>,
+}
+
+/// Collects weighted partial signatures in G1
+pub type WeightedCombiner<'a> = WeightedCombinerOf<'a, MinSig>;
+
+/// Collects weighted partial signatures in G2
+pub type WeightedCombinerVt<'a> = WeightedCombinerOf<'a, MinPk>;
+
+impl<'a, S: Scheme> WeightedCombinerOf<'a, S> {
+    /// Create a new combiner for signatures over `msg` requiring at least
+    /// `numerator / denominator` of the total weight to sign.
+    /// `share_keys` are the public keys of every share in order,
+    /// i.e. [`WeightedKeyShares::public_keys`] of every party in order.
+    /// The shares must have been split with the `T` given by
+    /// [`ShareAllocation::threshold`] for the same fraction.
+    pub fn new<B: AsRef<[u8]>>(
+        allocation: &'a ShareAllocation,
+        share_keys: &'a [PublicKeyOf<S>],
+        msg: B,
+        numerator: u64,
+        denominator: u64,
+    ) -> Self {
+        Self {
+            allocation,
+            share_keys,
+            msg: HashedMessageOf::new(msg),
+            numerator,
+            denominator,
+            signed: alloc::vec![false; allocation.parties()],
+            weight: 0,
+            partials: Vec::with_capacity(allocation.total_shares()),
+        }
+    }
+
+    /// Add the partial signatures from a party.
+    /// Fails if the party is unknown, already added, the partial signatures
+    /// don't match the shares allocated to the party, or any partial signature
+    /// doesn't verify with the public key of its share.
+    pub fn add(&mut self, signature: &WeightedPartialSignatureOf<S>) -> Result<(), Error> {
+        let range = self
+            .allocation
+            .share_range(signature.party)
+            .ok_or(Error::Sharing(vsss_rs::Error::InvalidShare))?;
+        if self.signed[signature.party] {
+            return Err(Error::Sharing(vsss_rs::Error::SharingDuplicateIdentifier));
+        }
+        if signature.partials.len() != range.len()
+            || self.share_keys.len() != self.allocation.total_shares()
+        {
+            return Err(Error::Sharing(vsss_rs::Error::InvalidShare));
+        }
+        // Share identifiers start at 1
+        let expected = (range.start + 1)..(range.end + 1);
+        let mut ids = Vec::with_capacity(range.len());
+        for p in &signature.partials {
+            let id = S::SignatureGroup::share_identifier(&p.0) as usize;
+            if !expected.contains(&id) {
+                return Err(Error::Sharing(vsss_rs::Error::SharingInvalidIdentifier));
+            }
+            if ids.contains(&id) {
+                return Err(Error::Sharing(vsss_rs::Error::SharingDuplicateIdentifier));
+            }
+            ids.push(id);
+            p.try_verify_prehashed(self.share_keys[id - 1], &self.msg)?;
+        }
+        self.signed[signature.party] = true;
+        self.weight += self.allocation.weight(signature.party);
+        self.partials.extend_from_slice(&signature.partials);
+        Ok(())
+    }
+
+    /// The weight accumulated so far
+    pub fn weight(&self) -> u64 {
+        self.weight
+    }
+
+    /// The number of partial signatures accumulated so far
+    pub fn shares(&self) -> usize {
+        self.partials.len()
+    }
+
+    /// The number of partial signatures needed to complete the signature
+    pub fn threshold(&self) -> usize {
+        self.allocation.threshold(self.numerator, self.denominator)
+    }
+
+    /// Has enough weight signed and are there enough partial signatures to reach
+    /// the threshold given by [`ShareAllocation::threshold`] for the same fraction
+    pub fn is_complete(&self) -> bool {
+        let total_weight = match self.allocation.total_weight() {
+            Some(w) => w,
+            None => return false,
+        };
+        (self.weight as u128) * (self.denominator as u128)
+            >= (total_weight as u128) * (self.numerator as u128)
+            && self.partials.len() >= self.threshold()
+    }
+
+    /// Combine the partial signatures into a completed signature
+    pub fn finalize(&self) -> Result<SignatureOf<S>, Error> {
+        if !self.is_complete() || self.partials.is_empty() {
+            return Err(Error::Sharing(
+                vsss_rs::Error::SharingLimitLessThanThreshold,
+            ));
+        }
+        // Every partial was verified when added so the points are valid
+        let points = self
+            .partials
+            .iter()
+            .map(|p| {
+                Option::<S::SignatureGroup>::from(S::SignatureGroup::share_point(&p.0))
+                    .map(|point| (S::SignatureGroup::share_identifier(&p.0), point))
+            })
+            .collect::<Option<Vec<(u8, S::SignatureGroup)>>>()
+            .ok_or(Error::Sharing(vsss_rs::Error::InvalidShare))?;
+        #[cfg(feature = "parallel")]
+        {
+            Ok(SignatureOf(crate::parallel::combine_shares_group(&points)?))
+        }
+        #[cfg(not(feature = "parallel"))]
+        {
+            Ok(SignatureOf(interpolate(&points)))
+        }
+    }
+}
+
+/// Interpolate the shares at zero.
+/// All the shares are used so any number at or above the threshold
+/// gives the same result. Identifiers must be distinct and not zero.
+#[cfg(not(feature = "parallel"))]
+fn interpolate<G: Group<Scalar = Scalar>>(shares: &[(u8, G)]) -> G {
+    let mut sum = G::identity();
+    for (i, point) in shares {
+        let xi = Scalar::from(*i as u64);
+        let mut num = Scalar::ONE;
+        let mut den = Scalar::ONE;
+        for (j, _) in shares.iter().filter(|(j, _)| j != i) {
+            let xj = Scalar::from(*j as u64);
+            num *= xj;
+            den *= xj - xi;
+        }
+        // identifiers are distinct so den is never zero
+        sum += *point * (num * den.invert().unwrap());
+    }
+    sum
+}
+
+#[test]
+fn weighted_threshold_works() {
+    use crate::{MockRng, PublicKey, PublicKeyVt};
+    use rand_core::SeedableRng;
+
+    let allocation = ShareAllocation::new(&[1, 1, 1], 10).unwrap();
+    assert_eq!(allocation.shares(0), 4);
+    assert_eq!(allocation.shares(1), 3);
+    assert_eq!(allocation.shares(2), 3);
+
+    let mut rng = MockRng::from_seed([13u8; 16]);
+    let sk = SecretKey::random(&mut rng).unwrap();
+    let pk = PublicKey::from(&sk);
+    let msg = b"weighted_threshold";
+
+    let allocation = ShareAllocation::new(&[5, 3, 1, 1], 10).unwrap();
+    assert_eq!(allocation.threshold(2, 3), 7);
+    let parties = allocation.split::<MockRng, 7, 10>(&sk, &mut rng).unwrap();
+    let keys = parties
+        .iter()
+        .flat_map(|p| p.public_keys::<MinSig>().unwrap())
+        .collect::<Vec<_>>();
+    let sigs = parties
+        .iter()
+        .map(|p| p.sign::<MinSig, _>(msg).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(sigs[1].partials[0].verify(keys[5], msg).unwrap_u8(), 1u8);
+    assert_eq!(sigs[1].partials[0].verify(keys[6], msg).unwrap_u8(), 0u8);
+
+    let mut combiner = WeightedCombiner::new(&allocation, &keys, msg, 2, 3);
+    assert_eq!(combiner.threshold(), 7);
+    assert!(combiner.add(&sigs[0]).is_ok());
+    assert!(combiner.add(&sigs[0]).is_err());
+    assert!(!combiner.is_complete());
+    assert!(combiner.finalize().is_err());
+    assert!(combiner.add(&sigs[2]).is_ok());
+    assert!(combiner.add(&sigs[3]).is_ok());
+    assert_eq!(combiner.weight(), 7);
+    let sig = combiner.finalize().unwrap();
+    assert_eq!(sig.verify(pk, msg).unwrap_u8(), 1u8);
+
+    let mut combiner = WeightedCombiner::new(&allocation, &keys, msg, 2, 3);
+    assert!(combiner.add(&sigs[1]).is_ok());
+    assert!(combiner.add(&sigs[2]).is_ok());
+    assert!(combiner.add(&sigs[3]).is_ok());
+    assert!(combiner.finalize().is_err());
+
+    // Partials attributed to the wrong party are rejected
+    let mut forged = sigs[1].clone();
+    forged.party = 2;
+    assert!(WeightedCombiner::new(&allocation, &keys, msg, 2, 3)
+        .add(&forged)
+        .is_err());
+
+    // Partials over another message are rejected when added
+    let bad = parties[1].sign::<MinSig, _>(b"another message").unwrap();
+    let mut combiner = WeightedCombiner::new(&allocation, &keys, msg, 2, 3);
+    assert!(matches!(combiner.add(&bad), Err(Error::VerificationFailed)));
+    assert_eq!(combiner.shares(), 0);
+
+    // Enough weight but too few shares to reach the threshold
+    let allocation = ShareAllocation::new(&[1, 1, 1], 10).unwrap();
+    assert_eq!(allocation.threshold(2, 3), 7);
+    let parties = allocation.split::<MockRng, 7, 10>(&sk, &mut rng).unwrap();
+    let keys = parties
+        .iter()
+        .flat_map(|p| p.public_keys::<MinSig>().unwrap())
+        .collect::<Vec<_>>();
+    let mut combiner = WeightedCombiner::new(&allocation, &keys, msg, 2, 3);
+    assert!(combiner
+        .add(&parties[1].sign::<MinSig, _>(msg).unwrap())
+        .is_ok());
+    assert!(combiner
+        .add(&parties[2].sign::<MinSig, _>(msg).unwrap())
+        .is_ok());
+    assert_eq!(combiner.shares(), 6);
+    assert!(!combiner.is_complete());
+    assert!(combiner.finalize().is_err());
+    assert!(combiner
+        .add(&parties[0].sign::<MinSig, _>(msg).unwrap())
+        .is_ok());
+    assert!(combiner.is_complete());
+    let sig = combiner.finalize().unwrap();
+    assert_eq!(sig.verify(pk, msg).unwrap_u8(), 1u8);
+
+    // The same shares sign in G2
+    let keys = parties
+        .iter()
+        .flat_map(|p| p.public_keys::<MinPk>().unwrap())
+        .collect::<Vec<_>>();
+    let mut combiner = WeightedCombinerVt::new(&allocation, &keys, msg, 2, 3);
+    for party in &parties {
+        assert!(combiner.add(&party.sign::<MinPk, _>(msg).unwrap()).is_ok());
+    }
+    let sig = combiner.finalize().unwrap();
+    assert_eq!(sig.verify(PublicKeyVt::from(&sk), msg).unwrap_u8(), 1u8);
+
+    let bytes = serde_bare::to_vec(&allocation).unwrap();
+    assert_eq!(
+        serde_bare::from_slice::<ShareAllocation>(&bytes).unwrap(),
+        allocation
+    );
+    let overflow = ShareAllocation {
+        weights: alloc::vec![u64::MAX, 1],
+        shares: alloc::vec![5, 5],
+    };
+    assert_eq!(overflow.total_weight(), None);
+    let bytes = serde_bare::to_vec(&overflow).unwrap();
+    assert!(serde_bare::from_slice::<ShareAllocation>(&bytes).is_err());
+}